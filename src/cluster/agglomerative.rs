@@ -0,0 +1,305 @@
+use crate::matrix::Matrix;
+use crate::neighbors::Metric;
+
+/// Distance between two clusters, from the distances between their samples.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Linkage {
+    /// Distance of the closest pair of samples.
+    Single,
+
+    /// Distance of the farthest pair of samples.
+    Complete,
+
+    /// Mean distance over all pairs of samples.
+    Average,
+
+    /// Increase of the within-cluster sum of squares brought by merging, as a distance (the
+    /// default). Requires the [Euclidean](Metric::Euclidean) metric.
+    #[default]
+    Ward,
+}
+
+/// Merge of two clusters, numbered like in SciPy: samples are the clusters `0..n`, and the
+/// cluster made by the `i`-th merge is `n + i`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct Merge {
+    pub(super) left: usize,
+    pub(super) right: usize,
+    pub(super) distance: f64,
+    pub(super) size: usize,
+}
+
+/// Agglomerative hierarchical clustering: starts from one cluster per sample, and repeatedly
+/// merges the two closest clusters until a single one is left.
+///
+/// The merges are found with the nearest-neighbor chain algorithm, in quadratic time and
+/// memory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Agglomerative {
+    linkage: Linkage,
+    metric: Metric,
+    pub(super) fitted: Option<Vec<Merge>>,
+}
+
+impl Agglomerative {
+    /// [Ward](Linkage::Ward) linkage of Euclidean distances.
+    pub fn new() -> Self {
+        Self {
+            linkage: Linkage::Ward,
+            metric: Metric::Euclidean,
+            fitted: None,
+        }
+    }
+
+    /// Linkage between clusters ([Ward](Linkage::Ward) by default).
+    pub fn with_linkage(mut self, linkage: Linkage) -> Self {
+        self.linkage = linkage;
+        self
+    }
+
+    /// Distance between samples ([Euclidean](Metric::Euclidean) by default).
+    pub fn with_metric(mut self, metric: Metric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Builds the merge tree of the rows of `x`.
+    pub fn fit(&mut self, x: &Matrix) {
+        assert!(
+            self.linkage != Linkage::Ward || self.metric == Metric::Euclidean,
+            "Ward linkage requires the Euclidean metric"
+        );
+        let n = x.num_rows();
+        assert!(n > 0, "No samples");
+
+        let mut distances: Matrix<f64> = Matrix::zeros(n, n);
+        for i in 0..n {
+            for j in 0..i {
+                let distance = self.metric.distance(x.row(i), x.row(j));
+                distances[(i, j)] = distance;
+                distances[(j, i)] = distance;
+            }
+        }
+
+        // Each cluster lives in the slot of one of its samples, and `sizes` is 0 for empty
+        // slots. Following nearest neighbors until two clusters are each other's nearest one
+        // finds a pair that every linkage here would merge at some point anyway.
+        let mut sizes = vec![1; n];
+        let mut merges = Vec::with_capacity(n.saturating_sub(1));
+        let mut chain: Vec<usize> = Vec::new();
+        while merges.len() + 1 < n {
+            if chain.is_empty() {
+                chain.push(sizes.iter().position(|&size| size > 0).unwrap());
+            }
+            let (a, b) = loop {
+                let a = *chain.last().unwrap();
+                let previous = chain.len().checked_sub(2).map(|k| chain[k]);
+                let mut nearest = previous;
+                let mut min = previous.map_or(f64::INFINITY, |b| distances[(a, b)]);
+                for c in (0..n).filter(|&c| c != a && sizes[c] > 0) {
+                    if distances[(a, c)] < min {
+                        min = distances[(a, c)];
+                        nearest = Some(c);
+                    }
+                }
+                let nearest = nearest.unwrap();
+                if Some(nearest) == previous {
+                    chain.truncate(chain.len() - 2);
+                    break (a, nearest);
+                }
+                chain.push(nearest);
+            };
+
+            // The merged cluster takes the slot of `b`.
+            let distance = distances[(a, b)];
+            let (size_a, size_b) = (sizes[a] as f64, sizes[b] as f64);
+            for c in (0..n).filter(|&c| c != a && c != b && sizes[c] > 0) {
+                let (to_a, to_b) = (distances[(c, a)], distances[(c, b)]);
+                let size_c = sizes[c] as f64;
+                let merged = match self.linkage {
+                    Linkage::Single => to_a.min(to_b),
+                    Linkage::Complete => to_a.max(to_b),
+                    Linkage::Average => (size_a * to_a + size_b * to_b) / (size_a + size_b),
+                    Linkage::Ward => (((size_a + size_c) * to_a * to_a
+                        + (size_b + size_c) * to_b * to_b
+                        - size_c * distance * distance)
+                        / (size_a + size_b + size_c))
+                        .sqrt(),
+                };
+                distances[(c, b)] = merged;
+                distances[(b, c)] = merged;
+            }
+            sizes[b] += sizes[a];
+            sizes[a] = 0;
+            merges.push(Merge {
+                left: a,
+                right: b,
+                distance,
+                size: sizes[b],
+            });
+        }
+
+        self.fitted = Some(number_clusters(merges, n));
+    }
+
+    fn merges(&self) -> &[Merge] {
+        self.fitted.as_ref().expect("Model not fitted")
+    }
+
+    /// Number of samples the model was fitted on.
+    pub(super) fn num_samples(&self) -> usize {
+        self.merges().len() + 1
+    }
+
+    /// SciPy-style linkage matrix, once fitted: one row per merge, in increasing distance,
+    /// holding the two merged clusters (the smaller number first), their distance and the
+    /// number of samples in the new cluster. Samples are the clusters `0..n`, and the cluster
+    /// made by the merge of row `i` is `n + i`.
+    pub fn linkage_matrix(&self) -> Option<Matrix<f64>> {
+        let merges = self.fitted.as_ref()?;
+        let rows: Vec<[f64; 4]> = merges
+            .iter()
+            .map(|m| [m.left as f64, m.right as f64, m.distance, m.size as f64])
+            .collect();
+        Some(Matrix::new(rows.len(), 4, rows.concat()))
+    }
+
+    /// Cluster of each sample when merging until `num_clusters` are left, numbered in order of
+    /// their first sample.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn cut_clusters(&self, num_clusters: usize) -> Vec<usize> {
+        let n = self.num_samples();
+        assert!(
+            (1..=n).contains(&num_clusters),
+            "Invalid number of clusters"
+        );
+        self.cut(n - num_clusters)
+    }
+
+    /// Cluster of each sample when applying only the merges up to `distance`, so that no two
+    /// samples of a cluster are further apart in the tree, numbered in order of their first
+    /// sample.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn cut_distance(&self, distance: f64) -> Vec<usize> {
+        let num_merges = self
+            .merges()
+            .iter()
+            .take_while(|m| m.distance <= distance)
+            .count();
+        self.cut(num_merges)
+    }
+
+    /// Cluster of each sample after the first `num_merges` merges.
+    fn cut(&self, num_merges: usize) -> Vec<usize> {
+        let n = self.num_samples();
+        let mut roots: Vec<usize> = (0..n + num_merges).collect();
+        for (i, m) in self.merges()[..num_merges].iter().enumerate() {
+            roots[m.left] = n + i;
+            roots[m.right] = n + i;
+        }
+        let root = |mut c: usize| {
+            while roots[c] != c {
+                c = roots[c];
+            }
+            c
+        };
+
+        let mut numbers: Vec<Option<usize>> = vec![None; n + num_merges];
+        let mut num_clusters = 0;
+        (0..n)
+            .map(|i| {
+                *numbers[root(i)].get_or_insert_with(|| {
+                    num_clusters += 1;
+                    num_clusters - 1
+                })
+            })
+            .collect()
+    }
+}
+
+impl Default for Agglomerative {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sorts the merges of slots by distance, keeping the order of ties, and renames the slots
+/// after the clusters they hold at that point.
+///
+/// Every linkage here is monotone, so a cluster is never merged at a smaller distance than the
+/// merge that made it, and the sort keeps each merge after those of its children.
+fn number_clusters(mut merges: Vec<Merge>, n: usize) -> Vec<Merge> {
+    merges.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    // Cluster held by each slot. The merged cluster goes into the slot of `right`.
+    let mut clusters: Vec<usize> = (0..n).collect();
+    for (i, merge) in merges.iter_mut().enumerate() {
+        let (left, right) = (clusters[merge.left], clusters[merge.right]);
+        clusters[merge.right] = n + i;
+        (merge.left, merge.right) = (left.min(right), left.max(right));
+    }
+    merges
+}
+
+#[test]
+fn linkages_on_a_line() {
+    let x = Matrix::from_column(&[0.0, 1.0, 5.0, 6.0, 20.0]);
+    let linkage_matrix = |linkage| {
+        let mut model = Agglomerative::new().with_linkage(linkage);
+        model.fit(&x);
+        model.linkage_matrix().unwrap()
+    };
+
+    let rows = |last_distances: [f64; 2]| {
+        let [d5, d6] = last_distances;
+        Matrix::new(
+            4,
+            4,
+            vec![
+                0.0, 1.0, 1.0, 2.0, //
+                2.0, 3.0, 1.0, 2.0, //
+                5.0, 6.0, d5, 4.0, //
+                4.0, 7.0, d6, 5.0,
+            ],
+        )
+    };
+    assert_eq!(linkage_matrix(Linkage::Single), rows([4.0, 14.0]));
+    assert_eq!(linkage_matrix(Linkage::Complete), rows([6.0, 20.0]));
+    assert_eq!(linkage_matrix(Linkage::Average), rows([5.0, 17.0]));
+
+    // Ward distances are `sqrt(2 n_a n_b / (n_a + n_b))` times the distance of the centroids.
+    let ward = linkage_matrix(Linkage::Ward);
+    assert!((ward[(2, 2)] - 50f64.sqrt()).abs() < 1e-12);
+    assert!((ward[(3, 2)] - 1.6f64.sqrt() * 17.0).abs() < 1e-12);
+
+    let mut model = Agglomerative::new().with_linkage(Linkage::Single);
+    model.fit(&x);
+    assert_eq!(model.cut_clusters(2), [0, 0, 0, 0, 1]);
+    assert_eq!(model.cut_distance(2.0), [0, 0, 1, 1, 2]);
+    assert_eq!(model.cut_clusters(5), [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn iris_ward() {
+    use crate::datasets::iris::{self, Iris};
+    use crate::datasets::{ClassLabel, Dataset};
+    use crate::metrics::clustering::adjusted_rand_score;
+
+    let (x, y) = Iris::to_xy(iris::DATA);
+    let species: Vec<usize> = y.iter().map(ClassLabel::index).collect();
+    let mut model = Agglomerative::new();
+    model.fit(&x);
+    assert!(adjusted_rand_score(&species, &model.cut_clusters(3)) > 0.7);
+
+    let linkage_matrix = model.linkage_matrix().unwrap();
+    assert_eq!(linkage_matrix[(148, 3)], 150.0);
+    assert!(linkage_matrix
+        .rows()
+        .zip(linkage_matrix.rows().skip(1))
+        .all(|(a, b)| a[2] <= b[2]));
+
+    let names: Vec<&str> = y.iter().map(|l| Iris::TARGET_NAMES[l.index()]).collect();
+    let text = model.to_text(Some(&names));
+    assert_eq!(text.lines().filter(|l| l.ends_with("setosa")).count(), 50);
+}
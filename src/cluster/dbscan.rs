@@ -0,0 +1,124 @@
+use super::Neighbors;
+use crate::matrix::Matrix;
+use crate::neighbors::Metric;
+
+/// Density-based clustering (DBSCAN): samples with at least `min_samples` samples within `eps`
+/// (themselves included) are core samples, clusters are the groups of core samples within
+/// `eps` of each other plus the samples within `eps` of them, and the other samples are noise.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dbscan {
+    eps: f64,
+    min_samples: usize,
+    metric: Metric,
+    fitted: Option<DbscanFit>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct DbscanFit {
+    labels: Vec<Option<usize>>,
+    core_samples: Vec<usize>,
+}
+
+impl Dbscan {
+    /// Neighborhoods of radius `eps`, with 5 samples for a core sample.
+    pub fn new(eps: f64) -> Self {
+        assert!(eps > 0.0, "Invalid eps");
+        Self {
+            eps,
+            min_samples: 5,
+            metric: Metric::Euclidean,
+            fitted: None,
+        }
+    }
+
+    /// Number of samples within `eps` of a core sample, itself included (5 by default).
+    pub fn with_min_samples(mut self, min_samples: usize) -> Self {
+        assert!(min_samples > 0, "Invalid number of samples");
+        self.min_samples = min_samples;
+        self
+    }
+
+    /// Distance between samples ([Euclidean](Metric::Euclidean) by default).
+    pub fn with_metric(mut self, metric: Metric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Clusters the rows of `x`.
+    pub fn fit(&mut self, x: &Matrix) {
+        let search = Neighbors::new(x, self.metric);
+        let neighborhoods: Vec<Vec<usize>> = x
+            .rows()
+            .map(|row| {
+                let found = search.within(row, self.eps);
+                found.into_iter().map(|(i, _)| i).collect()
+            })
+            .collect();
+        let is_core: Vec<bool> = neighborhoods
+            .iter()
+            .map(|neighbors| neighbors.len() >= self.min_samples)
+            .collect();
+
+        // Each cluster grows from the unlabeled core sample with the lowest index, and border
+        // samples join the first cluster that reaches them.
+        let mut labels = vec![None; x.num_rows()];
+        let mut num_clusters = 0;
+        for start in 0..x.num_rows() {
+            if !is_core[start] || labels[start].is_some() {
+                continue;
+            }
+            labels[start] = Some(num_clusters);
+            let mut stack = vec![start];
+            while let Some(i) = stack.pop() {
+                for &j in &neighborhoods[i] {
+                    if labels[j].is_none() {
+                        labels[j] = Some(num_clusters);
+                        if is_core[j] {
+                            stack.push(j);
+                        }
+                    }
+                }
+            }
+            num_clusters += 1;
+        }
+
+        self.fitted = Some(DbscanFit {
+            labels,
+            core_samples: (0..x.num_rows()).filter(|&i| is_core[i]).collect(),
+        });
+    }
+
+    /// Cluster of each training sample, or `None` for noise, once fitted.
+    pub fn labels(&self) -> Option<&[Option<usize>]> {
+        self.fitted.as_ref().map(|fitted| &fitted.labels[..])
+    }
+
+    /// Indices of the core samples, once fitted.
+    pub fn core_sample_indices(&self) -> Option<&[usize]> {
+        self.fitted.as_ref().map(|fitted| &fitted.core_samples[..])
+    }
+
+    /// Number of clusters found, once fitted.
+    pub fn num_clusters(&self) -> Option<usize> {
+        let fitted = self.fitted.as_ref()?;
+        Some(fitted.labels.iter().flatten().max().map_or(0, |&k| k + 1))
+    }
+}
+
+#[test]
+fn points_on_a_line() {
+    // Two dense groups, with a border sample on the right of the first one, and an outlier.
+    let x = Matrix::from_column(&[0.0, 0.5, 1.0, 1.5, 2.5, 10.0, 10.5, 11.0, 20.0]);
+    let mut dbscan = Dbscan::new(1.0).with_min_samples(3);
+    dbscan.fit(&x);
+
+    let labels = dbscan.labels().unwrap();
+    let expected = [0, 0, 0, 0, 0, 1, 1, 1].map(Some);
+    assert_eq!(labels[..8], expected);
+    assert_eq!(labels[8], None);
+    assert_eq!(
+        dbscan.core_sample_indices(),
+        Some(&[0, 1, 2, 3, 5, 6, 7][..])
+    );
+    assert_eq!(dbscan.num_clusters(), Some(2));
+}
@@ -0,0 +1,188 @@
+use super::agglomerative::Agglomerative;
+
+/// Horizontal space for each leaf of an SVG dendrogram.
+const LEAF_SPACING: f64 = 20.0;
+
+/// Height of the tree of an SVG dendrogram, from the leaves to the last merge.
+const TREE_HEIGHT: f64 = 200.0;
+
+/// Space around the tree of an SVG dendrogram, and left of it for the distance axis.
+const MARGIN: f64 = 10.0;
+const AXIS_WIDTH: f64 = 50.0;
+
+/// Rough width of a character of the 12px labels, to leave room for the longest one.
+const CHAR_WIDTH: f64 = 7.0;
+
+impl Agglomerative {
+    /// Labels of the samples: `leaf_labels` (e.g. the `TARGET_NAMES` of the class of each
+    /// sample), or the row indices.
+    fn leaf_labels(&self, leaf_labels: Option<&[&str]>) -> Vec<String> {
+        let n = self.num_samples();
+        match leaf_labels {
+            Some(labels) => {
+                assert_eq!(labels.len(), n, "Invalid number of labels");
+                labels.iter().map(|label| label.to_string()).collect()
+            }
+            None => (0..n).map(|i| i.to_string()).collect(),
+        }
+    }
+
+    /// Children of a merged cluster, the smaller number first.
+    fn children(&self, cluster: usize) -> Option<(usize, usize)> {
+        let merge = self
+            .fitted
+            .as_ref()?
+            .get(cluster.checked_sub(self.num_samples())?)?;
+        Some((merge.left, merge.right))
+    }
+
+    /// Last merged cluster, holding every sample.
+    fn root(&self) -> usize {
+        2 * self.num_samples() - 2
+    }
+
+    /// Samples from left to right in a dendrogram, as met by a depth-first walk from the root.
+    fn leaf_order(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.num_samples());
+        let mut stack = vec![self.root()];
+        while let Some(cluster) = stack.pop() {
+            match self.children(cluster) {
+                Some((left, right)) => stack.extend([right, left]),
+                None => order.push(cluster),
+            }
+        }
+        order
+    }
+
+    /// Distance at which `cluster` was made, 0 for samples.
+    fn height(&self, cluster: usize) -> f64 {
+        let merges = self.fitted.as_ref().expect("Model not fitted");
+        cluster
+            .checked_sub(self.num_samples())
+            .map_or(0.0, |i| merges[i].distance)
+    }
+
+    /// Merge tree as an indented outline, with each merge shown by its distance above the two
+    /// clusters it joined, and each sample by its label from `leaf_labels` (the row index if
+    /// `None`).
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn to_text(&self, leaf_labels: Option<&[&str]>) -> String {
+        let labels = self.leaf_labels(leaf_labels);
+        let mut text = String::new();
+        // Clusters to write, with the prefix of their first line and of the lines below.
+        let mut stack = vec![(self.root(), String::new(), String::new())];
+        while let Some((cluster, first, rest)) = stack.pop() {
+            match self.children(cluster) {
+                Some((left, right)) => {
+                    text += &format!("{first}{:.3}\n", self.height(cluster));
+                    stack.push((right, format!("{rest}└── "), format!("{rest}    ")));
+                    stack.push((left, format!("{rest}├── "), format!("{rest}│   ")));
+                }
+                None => text += &format!("{first}{}\n", labels[cluster]),
+            }
+        }
+        text
+    }
+
+    /// Merge tree as an SVG image, with the samples along the bottom, labelled from
+    /// `leaf_labels` (the row index if `None`), and each merge drawn at the height of its
+    /// distance.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn to_svg(&self, leaf_labels: Option<&[&str]>) -> String {
+        let labels = self.leaf_labels(leaf_labels);
+        let escape = |text: &str| {
+            text.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+        };
+
+        let n = self.num_samples();
+        let max_height = self.height(self.root());
+        let scale = if max_height > 0.0 {
+            TREE_HEIGHT / max_height
+        } else {
+            0.0
+        };
+        let y = |cluster: usize| MARGIN + TREE_HEIGHT - self.height(cluster) * scale;
+        let bottom = MARGIN + TREE_HEIGHT;
+        let label_height =
+            CHAR_WIDTH * labels.iter().map(|l| l.chars().count()).max().unwrap_or(0) as f64;
+        let width = AXIS_WIDTH + n as f64 * LEAF_SPACING + MARGIN;
+        let height = bottom + 2.0 * MARGIN + label_height;
+
+        // Horizontal position of each cluster: leaves are evenly spaced, and merged clusters
+        // sit above the middle of their children.
+        let mut x = vec![0.0; 2 * n - 1];
+        for (k, leaf) in self.leaf_order().into_iter().enumerate() {
+            x[leaf] = AXIS_WIDTH + (k as f64 + 0.5) * LEAF_SPACING;
+        }
+        for cluster in n..2 * n - 1 {
+            let (left, right) = self.children(cluster).unwrap();
+            x[cluster] = (x[left] + x[right]) / 2.0;
+        }
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.0}\" height=\"{height:.0}\" \
+             font-family=\"sans-serif\" font-size=\"12\">\n"
+        );
+        svg += &format!(
+            "  <g stroke=\"black\">\n    <line x1=\"{AXIS_WIDTH}\" y1=\"{MARGIN}\" x2=\"{AXIS_WIDTH}\" \
+             y2=\"{bottom}\"/>\n  </g>\n"
+        );
+        svg += "  <g text-anchor=\"end\" dominant-baseline=\"middle\">\n";
+        for (value, y) in [(max_height, MARGIN), (0.0, bottom)] {
+            svg += &format!(
+                "    <text x=\"{:.1}\" y=\"{y:.1}\">{value:.3}</text>\n",
+                AXIS_WIDTH - 4.0
+            );
+        }
+        svg += "  </g>\n";
+
+        // Each merge as a bracket from the top of its left child to the top of its right one.
+        svg += "  <g fill=\"none\" stroke=\"black\">\n";
+        for cluster in n..2 * n - 1 {
+            let (left, right) = self.children(cluster).unwrap();
+            svg += &format!(
+                "    <polyline points=\"{:.1},{:.1} {:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\"/>\n",
+                x[left],
+                y(left),
+                x[left],
+                y(cluster),
+                x[right],
+                y(cluster),
+                x[right],
+                y(right)
+            );
+        }
+        svg += "  </g>\n";
+
+        // Labels read upwards, ending just below their leaf.
+        svg += "  <g text-anchor=\"end\" dominant-baseline=\"middle\">\n";
+        let top = bottom + MARGIN / 2.0;
+        for (leaf, label) in labels.iter().enumerate() {
+            let x = x[leaf];
+            svg += &format!(
+                "    <text transform=\"translate({x:.1},{top:.1}) rotate(-90)\">{}</text>\n",
+                escape(label)
+            );
+        }
+        svg += "  </g>\n</svg>\n";
+        svg
+    }
+}
+
+#[test]
+fn dendrogram_golden_files() {
+    use crate::matrix::Matrix;
+
+    let x = Matrix::from_column(&[0.0, 1.0, 5.0, 6.0, 20.0]);
+    let mut model = Agglomerative::new();
+    model.fit(&x);
+    assert_eq!(model.leaf_order(), [4, 0, 1, 2, 3]);
+    assert_eq!(model.to_text(None), include_str!("golden/line.txt"));
+
+    let labels = ["a", "b", "c", "d", "<e>"];
+    assert_eq!(model.to_svg(Some(&labels)), include_str!("golden/line.svg"));
+}
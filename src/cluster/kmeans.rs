@@ -0,0 +1,458 @@
+use crate::matrix::Matrix;
+use crate::utils::random::Rng;
+
+fn squared_distance(center: &[f64], row: &[f32]) -> f64 {
+    center
+        .iter()
+        .zip(row)
+        .map(|(c, &x)| (c - x as f64).powi(2))
+        .sum()
+}
+
+/// Closest of the `centers` (rows) to `row`, and its squared distance.
+fn nearest(centers: &Matrix<f64>, row: &[f32]) -> (usize, f64) {
+    assert_eq!(row.len(), centers.num_cols(), "Invalid number of columns");
+    centers
+        .rows()
+        .map(|center| squared_distance(center, row))
+        .enumerate()
+        .fold(
+            (0, f64::INFINITY),
+            |best, (k, d)| if d < best.1 { (k, d) } else { best },
+        )
+}
+
+/// Closest center of each row of `x`, and the sum of the squared distances to them.
+fn assign(centers: &Matrix<f64>, x: &Matrix) -> (Vec<usize>, f64) {
+    let mut inertia = 0.0;
+    let labels = x
+        .rows()
+        .map(|row| {
+            let (k, distance) = nearest(centers, row);
+            inertia += distance;
+            k
+        })
+        .collect();
+    (labels, inertia)
+}
+
+/// Chooses `num_clusters` initial centers among the rows of `x` with greedy k-means++: each new
+/// center is the best of a few candidates drawn with probability proportional to their squared
+/// distance to the closest center so far.
+fn kmeans_plus_plus(x: &Matrix, num_clusters: usize, rng: &mut Rng) -> Matrix<f64> {
+    let n = x.num_rows();
+    assert!(
+        (1..=n).contains(&num_clusters),
+        "Invalid number of clusters"
+    );
+    let num_candidates = 2 + (num_clusters as f64).ln() as usize;
+    let to_center = |i: usize| x.row(i).iter().map(|&v| v as f64).collect::<Vec<_>>();
+
+    let mut centers = vec![to_center(rng.below(n))];
+    let mut distances: Vec<f64> = x
+        .rows()
+        .map(|row| squared_distance(&centers[0], row))
+        .collect();
+    while centers.len() < num_clusters {
+        let mut best: Option<(Vec<f64>, Vec<f64>, f64)> = None;
+        for _ in 0..num_candidates {
+            // Every remaining row coincides with a center when all distances are zero.
+            let candidate = if distances.iter().any(|&d| d > 0.0) {
+                rng.categorical(&distances)
+            } else {
+                rng.below(n)
+            };
+            let center = to_center(candidate);
+            let updated: Vec<f64> = x
+                .rows()
+                .zip(&distances)
+                .map(|(row, &d)| d.min(squared_distance(&center, row)))
+                .collect();
+            let potential: f64 = updated.iter().sum();
+            if best.as_ref().is_none_or(|best| potential < best.2) {
+                best = Some((center, updated, potential));
+            }
+        }
+        let (center, updated, _) = best.unwrap();
+        centers.push(center);
+        distances = updated;
+    }
+    Matrix::from_rows(&centers)
+}
+
+/// Mean of the variance of each column of `x`, which scales the convergence tolerance.
+fn mean_variance(x: &Matrix) -> f64 {
+    let n = x.num_rows() as f64;
+    let total: f64 = (0..x.num_cols())
+        .map(|j| {
+            let mean = x.col(j).map(|&v| v as f64).sum::<f64>() / n;
+            x.col(j).map(|&v| (v as f64 - mean).powi(2)).sum::<f64>() / n
+        })
+        .sum();
+    total / x.num_cols() as f64
+}
+
+/// Centers of a fitted clustering, with the cluster of each training sample.
+#[derive(Clone, Debug, PartialEq)]
+struct Clustering {
+    centers: Matrix<f64>,
+    labels: Vec<usize>,
+    inertia: f64,
+    num_iter: usize,
+}
+
+/// Accessors for the [Clustering] in the `fitted` field of an estimator.
+macro_rules! clustering_accessors {
+    () => {
+        /// Predicts the cluster of each row of `x`: the one with the closest center.
+        ///
+        /// Panics if the model has not been fitted.
+        pub fn predict(&self, x: &Matrix) -> Vec<usize> {
+            assign(&self.fitted.as_ref().expect("Model not fitted").centers, x).0
+        }
+
+        /// Center of each cluster (rows), once fitted.
+        pub fn centers(&self) -> Option<&Matrix<f64>> {
+            self.fitted.as_ref().map(|fitted| &fitted.centers)
+        }
+
+        /// Cluster of each training sample, once fitted.
+        pub fn labels(&self) -> Option<&[usize]> {
+            self.fitted.as_ref().map(|fitted| &fitted.labels[..])
+        }
+
+        /// Sum of the squared distances of the training samples to their center, once fitted.
+        pub fn inertia(&self) -> Option<f64> {
+            self.fitted.as_ref().map(|fitted| fitted.inertia)
+        }
+
+        /// Number of iterations of the kept run, once fitted.
+        pub fn num_iter(&self) -> Option<usize> {
+            self.fitted.as_ref().map(|fitted| fitted.num_iter)
+        }
+    };
+}
+
+/// k-means clustering: alternately assigns each sample to the closest of `k` centers and moves
+/// each center to the mean of its samples (Lloyd's algorithm), which minimizes the inertia.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KMeans {
+    num_clusters: usize,
+    num_init: usize,
+    max_iter: usize,
+    tol: f64,
+    seed: u64,
+    fitted: Option<Clustering>,
+}
+
+impl KMeans {
+    /// `num_clusters` clusters, keeping the best of 10 runs from k-means++ initial centers.
+    pub fn new(num_clusters: usize) -> Self {
+        Self {
+            num_clusters,
+            num_init: 10,
+            max_iter: 300,
+            tol: 1e-4,
+            seed: 0,
+            fitted: None,
+        }
+    }
+
+    /// Number of runs from different initial centers, of which the one with the lowest inertia
+    /// is kept (10 by default).
+    pub fn with_num_init(mut self, num_init: usize) -> Self {
+        assert!(num_init > 0, "Invalid number of runs");
+        self.num_init = num_init;
+        self
+    }
+
+    /// Maximum number of iterations of each run (300 by default).
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// A run stops once the centers move less than `tol` times the mean variance of the
+    /// features, in total squared distance (1e-4 by default).
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    /// Seed of the initial centers.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Clusters the rows of `x`.
+    pub fn fit(&mut self, x: &Matrix) {
+        let tol = self.tol * mean_variance(x);
+        let mut rng = Rng::new(self.seed);
+        let mut best: Option<Clustering> = None;
+        for _ in 0..self.num_init {
+            let centers = kmeans_plus_plus(x, self.num_clusters, &mut rng);
+            let run = self.lloyd(x, centers, tol);
+            if best.as_ref().is_none_or(|best| run.inertia < best.inertia) {
+                best = Some(run);
+            }
+        }
+        self.fitted = best;
+    }
+
+    fn lloyd(&self, x: &Matrix, mut centers: Matrix<f64>, tol: f64) -> Clustering {
+        let (mut labels, _) = assign(&centers, x);
+        let mut num_iter = 0;
+        while num_iter < self.max_iter {
+            num_iter += 1;
+            let mut sums: Matrix<f64> = Matrix::zeros(self.num_clusters, x.num_cols());
+            let mut counts = vec![0; self.num_clusters];
+            for (row, &k) in x.rows().zip(&labels) {
+                counts[k] += 1;
+                for (sum, &v) in sums.row_mut(k).iter_mut().zip(row) {
+                    *sum += v as f64;
+                }
+            }
+
+            let mut shift = 0.0;
+            for (k, &count) in counts.iter().enumerate() {
+                let center: Vec<f64> = if count > 0 {
+                    sums.row(k).iter().map(|s| s / count as f64).collect()
+                } else {
+                    // An empty cluster takes the sample farthest from its center.
+                    let farthest = (0..x.num_rows())
+                        .map(|i| (i, squared_distance(centers.row(labels[i]), x.row(i))))
+                        .fold(
+                            (0, -1.0),
+                            |best, (i, d)| if d > best.1 { (i, d) } else { best },
+                        )
+                        .0;
+                    labels[farthest] = k;
+                    x.row(farthest).iter().map(|&v| v as f64).collect()
+                };
+                shift += center
+                    .iter()
+                    .zip(centers.row(k))
+                    .map(|(a, b)| (a - b).powi(2))
+                    .sum::<f64>();
+                centers.row_mut(k).copy_from_slice(&center);
+            }
+
+            let (new_labels, _) = assign(&centers, x);
+            let unchanged = new_labels == labels;
+            labels = new_labels;
+            if unchanged || shift <= tol {
+                break;
+            }
+        }
+        let (labels, inertia) = assign(&centers, x);
+        Clustering {
+            centers,
+            labels,
+            inertia,
+            num_iter,
+        }
+    }
+
+    clustering_accessors!();
+}
+
+/// k-means on random mini-batches of the samples: each step moves the centers towards the
+/// samples of a batch assigned to them, with a step size that decreases as they gather samples.
+///
+/// Much faster than [KMeans] on large datasets, for a slightly higher inertia.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MiniBatchKMeans {
+    num_clusters: usize,
+    batch_size: usize,
+    num_init: usize,
+    max_iter: usize,
+    max_no_improvement: usize,
+    seed: u64,
+    fitted: Option<Clustering>,
+
+    /// Number of samples each center has gathered so far.
+    counts: Vec<usize>,
+    rng: Rng,
+}
+
+impl MiniBatchKMeans {
+    /// `num_clusters` clusters from batches of 1024 samples, keeping the best of 3 k-means++
+    /// initializations.
+    pub fn new(num_clusters: usize) -> Self {
+        Self {
+            num_clusters,
+            batch_size: 1024,
+            num_init: 3,
+            max_iter: 100,
+            max_no_improvement: 10,
+            seed: 0,
+            fitted: None,
+            counts: Vec::new(),
+            rng: Rng::new(0),
+        }
+    }
+
+    /// Number of samples in each batch (1024 by default).
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "Invalid batch size");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Number of k-means++ initializations, each on a random subset of three batches, of which
+    /// the one with the lowest inertia on that subset is kept (3 by default).
+    pub fn with_num_init(mut self, num_init: usize) -> Self {
+        assert!(num_init > 0, "Invalid number of initializations");
+        self.num_init = num_init;
+        self
+    }
+
+    /// Maximum number of passes over the samples (100 by default).
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Stops once the smoothed inertia of the batches has not improved for this many steps (10
+    /// by default).
+    pub fn with_max_no_improvement(mut self, max_no_improvement: usize) -> Self {
+        self.max_no_improvement = max_no_improvement;
+        self
+    }
+
+    /// Seed of the initial centers and of the batches.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Clusters the rows of `x`, from scratch.
+    pub fn fit(&mut self, x: &Matrix) {
+        let n = x.num_rows();
+        self.rng = Rng::new(self.seed);
+        self.initialize(x);
+
+        let batch_size = self.batch_size.min(n);
+        let num_steps = (self.max_iter * n).div_ceil(batch_size);
+        let mut smoothed: Option<f64> = None;
+        let mut best = f64::INFINITY;
+        let mut num_stale = 0;
+        let mut num_iter = 0;
+        for _ in 0..num_steps {
+            num_iter += 1;
+            let batch = x.select_rows(&self.rng.sample(n, batch_size));
+            let inertia = self.step(&batch) / batch_size as f64;
+
+            // Exponentially weighted average over about the last pass.
+            let alpha = (2.0 * batch_size as f64 / (n as f64 + 1.0)).min(1.0);
+            let value = smoothed.map_or(inertia, |s| s * (1.0 - alpha) + inertia * alpha);
+            smoothed = Some(value);
+            if value < best {
+                best = value;
+                num_stale = 0;
+            } else {
+                num_stale += 1;
+                if num_stale >= self.max_no_improvement {
+                    break;
+                }
+            }
+        }
+        self.finish(x, num_iter);
+    }
+
+    /// Updates the clustering with one batch of samples, initializing it from the batch if
+    /// needed. The [labels](Self::labels) and [inertia](Self::inertia) are then those of the
+    /// batch.
+    pub fn partial_fit(&mut self, batch: &Matrix) {
+        if self.fitted.is_none() {
+            self.rng = Rng::new(self.seed);
+            self.initialize(batch);
+        }
+        self.step(batch);
+        let num_iter = self.fitted.as_ref().unwrap().num_iter + 1;
+        self.finish(batch, num_iter);
+    }
+
+    fn initialize(&mut self, x: &Matrix) {
+        let init_size = (3 * self.batch_size)
+            .max(self.num_clusters)
+            .min(x.num_rows());
+        let mut best: Option<(Matrix<f64>, f64)> = None;
+        for _ in 0..self.num_init {
+            let subset = x.select_rows(&self.rng.sample(x.num_rows(), init_size));
+            let centers = kmeans_plus_plus(&subset, self.num_clusters, &mut self.rng);
+            let (_, inertia) = assign(&centers, &subset);
+            if best.as_ref().is_none_or(|best| inertia < best.1) {
+                best = Some((centers, inertia));
+            }
+        }
+        self.counts = vec![0; self.num_clusters];
+        self.fitted = Some(Clustering {
+            centers: best.unwrap().0,
+            labels: Vec::new(),
+            inertia: 0.0,
+            num_iter: 0,
+        });
+    }
+
+    /// Moves each center towards the samples of `batch` assigned to it, and returns the
+    /// inertia of the batch before the move.
+    fn step(&mut self, batch: &Matrix) -> f64 {
+        let fitted = self.fitted.as_mut().unwrap();
+        let (labels, inertia) = assign(&fitted.centers, batch);
+        for (row, &k) in batch.rows().zip(&labels) {
+            self.counts[k] += 1;
+            let rate = 1.0 / self.counts[k] as f64;
+            for (c, &v) in fitted.centers.row_mut(k).iter_mut().zip(row) {
+                *c += rate * (v as f64 - *c);
+            }
+        }
+        inertia
+    }
+
+    /// Labels the samples of `x` with the final centers.
+    fn finish(&mut self, x: &Matrix, num_iter: usize) {
+        let fitted = self.fitted.as_mut().unwrap();
+        (fitted.labels, fitted.inertia) = assign(&fitted.centers, x);
+        fitted.num_iter = num_iter;
+    }
+
+    clustering_accessors!();
+}
+
+#[test]
+fn iris_and_digits() {
+    use crate::datasets::digits::{self, Digits};
+    use crate::datasets::iris::{self, Iris};
+    use crate::datasets::{ClassLabel, Dataset};
+    use crate::metrics::clustering::adjusted_rand_score;
+
+    let (x, y) = Iris::to_xy(iris::DATA);
+    let species: Vec<usize> = y.iter().map(ClassLabel::index).collect();
+    let mut kmeans = KMeans::new(3);
+    kmeans.fit(&x);
+
+    // The optimum found by scikit-learn, where setosa is a cluster of its own.
+    assert!((kmeans.inertia().unwrap() - 78.851).abs() < 1e-2);
+    let labels = kmeans.labels().unwrap();
+    assert!(adjusted_rand_score(&species, labels) > 0.7);
+    assert_eq!(kmeans.predict(&x), labels);
+
+    let mut mini_batch = MiniBatchKMeans::new(3).with_batch_size(30);
+    mini_batch.fit(&x);
+    assert!(mini_batch.inertia().unwrap() < 1.1 * kmeans.inertia().unwrap());
+    assert!(adjusted_rand_score(&species, mini_batch.labels().unwrap()) > 0.65);
+
+    let (x, y) = Digits::to_xy(&digits::DATA);
+    let digits: Vec<usize> = y.iter().map(ClassLabel::index).collect();
+    let mut kmeans = KMeans::new(10).with_num_init(3);
+    kmeans.fit(&x);
+    assert!(adjusted_rand_score(&digits, kmeans.labels().unwrap()) > 0.5);
+
+    let mut mini_batch = MiniBatchKMeans::new(10).with_batch_size(100);
+    for batch in x.rows().collect::<Vec<_>>().chunks(100) {
+        mini_batch.partial_fit(&Matrix::from_rows(batch));
+    }
+    assert!(adjusted_rand_score(&digits, &mini_batch.predict(&x)) > 0.4);
+}
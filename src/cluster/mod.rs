@@ -0,0 +1,60 @@
+use crate::matrix::Matrix;
+use crate::neighbors::{BallTree, Metric};
+
+mod agglomerative;
+mod dbscan;
+mod dendrogram;
+mod kmeans;
+mod optics;
+
+pub use agglomerative::{Agglomerative, Linkage};
+pub use dbscan::Dbscan;
+pub use kmeans::{KMeans, MiniBatchKMeans};
+pub use optics::Optics;
+
+/// Neighbor search among the rows of a matrix: a [BallTree], or a brute-force search for the
+/// [Cosine](Metric::Cosine) metric, which the trees do not support.
+enum Neighbors {
+    Tree(BallTree),
+    BruteForce(Matrix, Metric),
+}
+
+impl Neighbors {
+    fn new(x: &Matrix, metric: Metric) -> Self {
+        match metric {
+            Metric::Cosine => Neighbors::BruteForce(x.clone(), metric),
+            _ => Neighbors::Tree(BallTree::new(x.clone(), metric)),
+        }
+    }
+
+    /// Row index and distance of every row within `radius` of `point`, from nearest to
+    /// farthest.
+    fn within(&self, point: &[f32], radius: f64) -> Vec<(usize, f64)> {
+        match self {
+            Neighbors::Tree(tree) => tree.query_radius(point, radius),
+            Neighbors::BruteForce(x, metric) => {
+                let mut found: Vec<(usize, f64)> = x
+                    .rows()
+                    .map(|row| metric.distance(point, row))
+                    .enumerate()
+                    .filter(|&(_, distance)| distance <= radius)
+                    .collect();
+                found.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+                found
+            }
+        }
+    }
+
+    /// Distance from `point` to its `k`-th nearest row.
+    fn kth_distance(&self, point: &[f32], k: usize) -> f64 {
+        match self {
+            Neighbors::Tree(tree) => tree.query(point, k)[k - 1].1,
+            Neighbors::BruteForce(x, metric) => {
+                let mut distances: Vec<f64> =
+                    x.rows().map(|row| metric.distance(point, row)).collect();
+                distances.sort_by(f64::total_cmp);
+                distances[k - 1]
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use super::Neighbors;
+use crate::matrix::Matrix;
+use crate::neighbors::Metric;
+
+/// Ordering points to identify the clustering structure (OPTICS): orders the samples so that
+/// dense groups are contiguous, and records the reachability distance at which each sample is
+/// reached from the previous ones.
+///
+/// Valleys of the reachability plot are clusters. Cutting it at any `eps` up to `max_eps` gives
+/// the clusters of [Dbscan](super::Dbscan) with that `eps` (up to some border samples), without
+/// refitting.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Optics {
+    min_samples: usize,
+    max_eps: f64,
+    metric: Metric,
+    fitted: Option<OpticsFit>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct OpticsFit {
+    ordering: Vec<usize>,
+    reachability: Vec<f64>,
+    core_distances: Vec<f64>,
+    predecessors: Vec<Option<usize>>,
+}
+
+impl Optics {
+    /// Core samples with 5 samples in their neighborhood, of unlimited radius.
+    pub fn new() -> Self {
+        Self {
+            min_samples: 5,
+            max_eps: f64::INFINITY,
+            metric: Metric::Euclidean,
+            fitted: None,
+        }
+    }
+
+    /// Number of samples in the neighborhood of a core sample, itself included (5 by
+    /// default).
+    pub fn with_min_samples(mut self, min_samples: usize) -> Self {
+        assert!(min_samples > 0, "Invalid number of samples");
+        self.min_samples = min_samples;
+        self
+    }
+
+    /// Largest neighborhood radius considered (unlimited by default). Smaller values are
+    /// faster, but leave samples farther apart unreachable.
+    pub fn with_max_eps(mut self, max_eps: f64) -> Self {
+        assert!(max_eps > 0.0, "Invalid eps");
+        self.max_eps = max_eps;
+        self
+    }
+
+    /// Distance between samples ([Euclidean](Metric::Euclidean) by default).
+    pub fn with_metric(mut self, metric: Metric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Orders the rows of `x`.
+    pub fn fit(&mut self, x: &Matrix) {
+        let n = x.num_rows();
+        assert!(self.min_samples <= n, "Fewer samples than min_samples");
+        let search = Neighbors::new(x, self.metric);
+        let core_distances: Vec<f64> = x
+            .rows()
+            .map(|row| search.kth_distance(row, self.min_samples))
+            .map(|d| if d <= self.max_eps { d } else { f64::INFINITY })
+            .collect();
+
+        let mut reachability = vec![f64::INFINITY; n];
+        let mut predecessors = vec![None; n];
+        let mut processed = vec![false; n];
+        let mut ordering = Vec::with_capacity(n);
+        for _ in 0..n {
+            // The closest unprocessed sample to those already ordered, the lowest index on ties
+            // (which starts a new group when nothing is reachable).
+            let next = (0..n)
+                .filter(|&i| !processed[i])
+                .min_by(|&a, &b| reachability[a].total_cmp(&reachability[b]))
+                .unwrap();
+            processed[next] = true;
+            ordering.push(next);
+            if core_distances[next].is_infinite() {
+                continue;
+            }
+            for (j, distance) in search.within(x.row(next), self.max_eps) {
+                let reach = distance.max(core_distances[next]);
+                if !processed[j] && reach < reachability[j] {
+                    reachability[j] = reach;
+                    predecessors[j] = Some(next);
+                }
+            }
+        }
+
+        self.fitted = Some(OpticsFit {
+            ordering,
+            reachability,
+            core_distances,
+            predecessors,
+        });
+    }
+
+    fn fitted(&self) -> &OpticsFit {
+        self.fitted.as_ref().expect("Model not fitted")
+    }
+
+    /// Clusters of the samples for a neighborhood radius of `eps`, or `None` for noise, as
+    /// [Dbscan](super::Dbscan) with the same `min_samples` would find them, except for border
+    /// samples ordered before any core sample that reaches them, which are noise.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn labels(&self, eps: f64) -> Vec<Option<usize>> {
+        let fitted = self.fitted();
+        assert!(eps <= self.max_eps, "eps is larger than max_eps");
+        let mut labels = vec![None; fitted.ordering.len()];
+        let mut num_clusters = 0;
+        for &i in &fitted.ordering {
+            if fitted.reachability[i] <= eps {
+                labels[i] = Some(num_clusters - 1);
+            } else if fitted.core_distances[i] <= eps {
+                labels[i] = Some(num_clusters);
+                num_clusters += 1;
+            }
+        }
+        labels
+    }
+
+    /// Indices of the core samples for a neighborhood radius of `eps`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn core_sample_indices(&self, eps: f64) -> Vec<usize> {
+        let core_distances = &self.fitted().core_distances;
+        (0..core_distances.len())
+            .filter(|&i| core_distances[i] <= eps)
+            .collect()
+    }
+
+    /// Indices of the samples in cluster order, once fitted.
+    pub fn ordering(&self) -> Option<&[usize]> {
+        self.fitted.as_ref().map(|fitted| &fitted.ordering[..])
+    }
+
+    /// Reachability distance of each sample (infinite for the first of each group), in sample
+    /// order, once fitted.
+    pub fn reachability(&self) -> Option<&[f64]> {
+        self.fitted.as_ref().map(|fitted| &fitted.reachability[..])
+    }
+
+    /// Distance of each sample to its `min_samples`-th nearest sample (infinite beyond
+    /// `max_eps`), once fitted.
+    pub fn core_distances(&self) -> Option<&[f64]> {
+        self.fitted
+            .as_ref()
+            .map(|fitted| &fitted.core_distances[..])
+    }
+
+    /// Sample from which each sample was reached, once fitted.
+    pub fn predecessors(&self) -> Option<&[Option<usize>]> {
+        self.fitted.as_ref().map(|fitted| &fitted.predecessors[..])
+    }
+
+    /// Text reachability plot: one line per sample in cluster order, with its index, its
+    /// reachability distance and a bar of up to `width` characters, scaled to the largest
+    /// finite distance. Unreachable samples, which start a group, have a full bar of `|`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn reachability_plot(&self, width: usize) -> String {
+        let fitted = self.fitted();
+        let max = fitted
+            .reachability
+            .iter()
+            .filter(|d| d.is_finite())
+            .fold(0.0, |max: f64, &d| max.max(d));
+        let digits = fitted.ordering.len().saturating_sub(1).to_string().len();
+
+        let mut plot = String::new();
+        for &i in &fitted.ordering {
+            let reach = fitted.reachability[i];
+            let line = if reach.is_finite() {
+                let length = if max > 0.0 {
+                    (reach / max * width as f64).round() as usize
+                } else {
+                    0
+                };
+                format!("{i:>digits$} {reach:>9.3} {}", "#".repeat(length))
+            } else {
+                format!("{i:>digits$} {:>9} {}", "inf", "|".repeat(width))
+            };
+            plot += line.trim_end();
+            plot += "\n";
+        }
+        plot
+    }
+}
+
+impl Default for Optics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn points_on_a_line() {
+    let x = Matrix::from_column(&[0.0, 0.5, 1.0, 1.5, 2.5, 10.0, 10.5, 11.0, 20.0]);
+    let mut optics = Optics::new().with_min_samples(3);
+    optics.fit(&x);
+
+    assert_eq!(optics.ordering(), Some(&[0, 1, 2, 3, 4, 5, 6, 7, 8][..]));
+    // Each group starts at its lowest index, and the outlier is only reached from afar.
+    assert_eq!(
+        optics.reachability_plot(18),
+        concat!(
+            "0       inf ||||||||||||||||||\n",
+            "1     1.000 ##\n",
+            "2     0.500 #\n",
+            "3     0.500 #\n",
+            "4     1.000 ##\n",
+            "5     7.500 ###############\n",
+            "6     1.000 ##\n",
+            "7     0.500 #\n",
+            "8     9.000 ##################\n",
+        )
+    );
+    let mut labels = [0, 0, 0, 0, 0, 1, 1, 1].map(Some).to_vec();
+    labels.push(None);
+    assert_eq!(optics.labels(1.0), labels);
+}
+
+#[test]
+fn wine_outliers_match_dbscan() {
+    use super::Dbscan;
+    use crate::datasets::wine::{self, Wine};
+    use crate::datasets::Dataset;
+    use crate::metrics::clustering::adjusted_rand_score;
+    use crate::utils::scale::{Scaler, StandardScaler};
+
+    let (x, _) = Wine::to_xy(&wine::DATA);
+    let x = Matrix::from_rows(&StandardScaler::new().fit_transform(&x.rows().collect::<Vec<_>>()));
+    let mut optics = Optics::new().with_min_samples(4);
+    optics.fit(&x);
+
+    for eps in [2.0, 2.5] {
+        let mut dbscan = Dbscan::new(eps).with_min_samples(4);
+        dbscan.fit(&x);
+        let core = dbscan.core_sample_indices().unwrap();
+        assert_eq!(optics.core_sample_indices(eps), core);
+
+        // Core samples fall in the same clusters, and some samples are outliers.
+        let labels = optics.labels(eps);
+        let core_labels = |labels: &[Option<usize>]| -> Vec<usize> {
+            core.iter().map(|&i| labels[i].unwrap()).collect()
+        };
+        let score = adjusted_rand_score(
+            &core_labels(&labels),
+            &core_labels(dbscan.labels().unwrap()),
+        );
+        assert_eq!(score, 1.0);
+        assert!(dbscan.labels().unwrap().iter().any(Option::is_none));
+    }
+}
@@ -1,3 +1,6 @@
+use super::{ClassLabel, Dataset};
+use crate::frame::{Column, DataFrame};
+
 /// [Breast Cancer Wisconsin dataset](https://github.com/scikit-learn/scikit-learn/blob/dc580a8ef5ee2a8aea80498388690e2213118efd/sklearn/datasets/descr/breast_cancer.rst) with 30 numeric, predictive attributes and the class.
 ///
 /// Features are computed from a digitized image of a fine needle aspirate (FNA) of a breast mass. They describe characteristics of the cell nuclei present in the image.
@@ -112,6 +115,20 @@ impl Label {
     }
 }
 
+impl ClassLabel for Label {
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        match index {
+            0 => Self::Malignant,
+            1 => Self::Benign,
+            _ => panic!("Invalid index"),
+        }
+    }
+}
+
 impl BreastCancer {
     pub const NUM_FEATURES: usize = 30;
     pub const FEATURE_NAMES: [&str; Self::NUM_FEATURES] = [
@@ -147,6 +164,70 @@ impl BreastCancer {
         "worst fractal dimension",
     ];
     pub const TARGET_NAMES: [&str; 2] = [Label::Malignant.to_str(), Label::Benign.to_str()];
+
+    /// Returns the dataset as a frame of the features and a categorical `target` column.
+    pub fn get_as_frame() -> DataFrame {
+        let mut df = DataFrame::from_features(&DATA);
+        df.insert(
+            "target",
+            Column::label(
+                &Self::TARGET_NAMES,
+                DATA.iter().map(|row| row.label as usize).collect(),
+            ),
+        );
+        df
+    }
+}
+
+impl Dataset for BreastCancer {
+    type Target = Label;
+
+    const NUM_FEATURES: usize = Self::NUM_FEATURES;
+    const FEATURE_NAMES: &'static [&'static str] = &Self::FEATURE_NAMES;
+    const TARGET_NAMES: &'static [&'static str] = &Self::TARGET_NAMES;
+
+    fn data() -> &'static [Self] {
+        &DATA
+    }
+
+    fn write_features(&self, row: &mut [f32]) {
+        row.copy_from_slice(&[
+            self.radius_mean,
+            self.texture_mean,
+            self.perimeter_mean,
+            self.area_mean,
+            self.smoothness_mean,
+            self.compactness_mean,
+            self.concavity_mean,
+            self.concave_points_mean,
+            self.symmetry_mean,
+            self.fractal_dimension_mean,
+            self.radius_standard,
+            self.texture_standard,
+            self.perimeter_standard,
+            self.area_standard,
+            self.smoothness_standard,
+            self.compactness_standard,
+            self.concavity_standard,
+            self.concave_points_standard,
+            self.symmetry_standard,
+            self.fractal_dimension_standard,
+            self.radius_worst,
+            self.texture_worst,
+            self.perimeter_worst,
+            self.area_worst,
+            self.smoothness_worst,
+            self.compactness_worst,
+            self.concavity_worst,
+            self.concave_points_worst,
+            self.symmetry_worst,
+            self.fractal_dimension_worst,
+        ]);
+    }
+
+    fn target(&self) -> Label {
+        self.label
+    }
 }
 
 macro_rules! breast_cancer_row {
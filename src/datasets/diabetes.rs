@@ -1,5 +1,7 @@
 use std::ops::{Index, IndexMut};
 
+use super::Dataset;
+use crate::frame::DataFrame;
 use crate::utils::scale;
 
 /// [Diabetes dataset](https://github.com/scikit-learn/scikit-learn/blob/dc580a8ef5ee2a8aea80498388690e2213118efd/sklearn/datasets/descr/diabetes.rst) with 10 features and a target value, typically used for regression.
@@ -94,29 +96,62 @@ impl Diabetes {
         "ltg (possibly log of serum triglycerides level)",
         "glu (blood sugar level)",
     ];
+    pub const TARGET_NAMES: [&str; 1] = ["target"];
 
+    /// Returns a copy of the dataset.
+    ///
+    /// When `scaled` is `true`, the features are scaled the way scikit-learn ships them: each
+    /// column is mean-centered and divided by its standard deviation times the square root of
+    /// the number of samples, so that its sum of squares is 1. The target is left as-is.
     pub fn get_as_vec(scaled: bool) -> Vec<Self> {
-        let data = DATA.to_vec();
+        let mut data = DATA.to_vec();
 
         if scaled {
+            let norm = (data.len() as f32).sqrt();
             for i in 0..Self::NUM_FEATURES {
-                let col = data.iter().map(|row| row[i]).collect();
-                let col_scaled = scale::scale(&col);
+                let col: Vec<f32> = data.iter().map(|row| row[i]).collect();
+                for (row, value) in data.iter_mut().zip(scale::scale(&col)) {
+                    row[i] = value / norm;
+                }
             }
-
-            todo!("Implement scaling");
         }
 
         data
     }
 
-    // pub fn get_as_frame(scaled: bool) -> DataFrame {
-    //     let mut df = DataFrame::new();
-    //     for (i, feature) in Self::FEATURE_NAMES.iter().enumerate() {
-    //         df.insert(feature, DATA.iter().map(|row| row[i]).collect());
-    //     }
-    //     df
-    // }
+    /// Returns the dataset as a frame of the features and an `i32` `target` column.
+    pub fn get_as_frame(scaled: bool) -> DataFrame {
+        let data = Self::get_as_vec(scaled);
+
+        let mut df = DataFrame::from_features(&data);
+        df.insert(
+            Self::TARGET_NAMES[0],
+            data.iter().map(|row| row.target).collect::<Vec<_>>(),
+        );
+        df
+    }
+}
+
+impl Dataset for Diabetes {
+    type Target = i32;
+
+    const NUM_FEATURES: usize = Self::NUM_FEATURES;
+    const FEATURE_NAMES: &'static [&'static str] = &Self::FEATURE_NAMES;
+    const TARGET_NAMES: &'static [&'static str] = &Self::TARGET_NAMES;
+
+    fn data() -> &'static [Self] {
+        &DATA
+    }
+
+    fn write_features(&self, row: &mut [f32]) {
+        for (i, value) in row.iter_mut().enumerate() {
+            *value = self[i];
+        }
+    }
+
+    fn target(&self) -> i32 {
+        self.target
+    }
 }
 
 macro_rules! diabetes_row {
@@ -714,3 +749,83 @@ pub const DATA: [Diabetes; 442] = [
     diabetes_row!(36.0, 1.0, 30.0, 95.0, 201.0, 125.2, 42.0, 4.79, 5.1299, 85.0, 220.0),
     diabetes_row!(36.0, 1.0, 19.6, 71.0, 250.0, 133.2, 97.0, 3.0, 4.5951, 92.0, 57.0),
 ];
+
+#[test]
+fn scaled_like_sklearn() {
+    // Rows of `sklearn.datasets.load_diabetes(scaled=True).data`.
+    let expected: [(usize, [f32; Diabetes::NUM_FEATURES]); 4] = [
+        (
+            0,
+            [
+                0.03807591,
+                0.05068012,
+                0.06169621,
+                0.02187239,
+                -0.0442235,
+                -0.03482076,
+                -0.04340085,
+                -0.00259226,
+                0.01990749,
+                -0.01764613,
+            ],
+        ),
+        (
+            1,
+            [
+                -0.00188202,
+                -0.04464164,
+                -0.05147406,
+                -0.02632753,
+                -0.00844872,
+                -0.01916334,
+                0.07441156,
+                -0.03949338,
+                -0.06833155,
+                -0.09220405,
+            ],
+        ),
+        (
+            100,
+            [
+                0.01628068,
+                -0.04464164,
+                0.01750591,
+                -0.02288468,
+                0.06034892,
+                0.0444058,
+                0.03023191,
+                -0.00259226,
+                0.03723625,
+                -0.0010777,
+            ],
+        ),
+        (
+            441,
+            [
+                -0.04547248,
+                -0.04464164,
+                -0.0730303,
+                -0.08141314,
+                0.08374012,
+                0.02780893,
+                0.17381578,
+                -0.03949338,
+                -0.00422151,
+                0.00306441,
+            ],
+        ),
+    ];
+
+    let data = Diabetes::get_as_vec(true);
+    for (i, row) in expected {
+        for (j, value) in row.iter().enumerate() {
+            assert!((data[i][j] - value).abs() < 1e-6, "row {i}, column {j}");
+        }
+        assert_eq!(data[i].target, DATA[i].target);
+    }
+
+    for j in 0..Diabetes::NUM_FEATURES {
+        let sum_of_squares: f32 = data.iter().map(|row| row[j] * row[j]).sum();
+        assert!((sum_of_squares - 1.0).abs() < 1e-5);
+    }
+}
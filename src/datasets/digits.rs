@@ -1,3 +1,6 @@
+use super::Dataset;
+use crate::frame::DataFrame;
+
 /// [Optical recognition of handwritten digits dataset](https://github.com/scikit-learn/scikit-learn/blob/dc580a8ef5ee2a8aea80498388690e2213118efd/sklearn/datasets/descr/digits.rst) with 1797 samples and 64 features (8x8 image).
 ///
 /// The data set contains images of hand-written digits: 10 classes where each class refers to a digit.
@@ -12,6 +15,111 @@ pub struct Digits {
 
 impl Digits {
     pub const NUM_FEATURES: usize = 64;
+    pub const FEATURE_NAMES: [&str; Self::NUM_FEATURES] = [
+        "pixel_0_0",
+        "pixel_0_1",
+        "pixel_0_2",
+        "pixel_0_3",
+        "pixel_0_4",
+        "pixel_0_5",
+        "pixel_0_6",
+        "pixel_0_7",
+        "pixel_1_0",
+        "pixel_1_1",
+        "pixel_1_2",
+        "pixel_1_3",
+        "pixel_1_4",
+        "pixel_1_5",
+        "pixel_1_6",
+        "pixel_1_7",
+        "pixel_2_0",
+        "pixel_2_1",
+        "pixel_2_2",
+        "pixel_2_3",
+        "pixel_2_4",
+        "pixel_2_5",
+        "pixel_2_6",
+        "pixel_2_7",
+        "pixel_3_0",
+        "pixel_3_1",
+        "pixel_3_2",
+        "pixel_3_3",
+        "pixel_3_4",
+        "pixel_3_5",
+        "pixel_3_6",
+        "pixel_3_7",
+        "pixel_4_0",
+        "pixel_4_1",
+        "pixel_4_2",
+        "pixel_4_3",
+        "pixel_4_4",
+        "pixel_4_5",
+        "pixel_4_6",
+        "pixel_4_7",
+        "pixel_5_0",
+        "pixel_5_1",
+        "pixel_5_2",
+        "pixel_5_3",
+        "pixel_5_4",
+        "pixel_5_5",
+        "pixel_5_6",
+        "pixel_5_7",
+        "pixel_6_0",
+        "pixel_6_1",
+        "pixel_6_2",
+        "pixel_6_3",
+        "pixel_6_4",
+        "pixel_6_5",
+        "pixel_6_6",
+        "pixel_6_7",
+        "pixel_7_0",
+        "pixel_7_1",
+        "pixel_7_2",
+        "pixel_7_3",
+        "pixel_7_4",
+        "pixel_7_5",
+        "pixel_7_6",
+        "pixel_7_7",
+    ];
+    pub const TARGET_NAMES: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+    /// Returns the dataset as a frame of `u8` pixel columns and a `u8` `target` column.
+    pub fn get_as_frame() -> DataFrame {
+        let mut df = DataFrame::new();
+        for (i, feature) in Self::FEATURE_NAMES.iter().enumerate() {
+            df.insert(
+                feature,
+                DATA.iter().map(|row| row.pixels[i]).collect::<Vec<_>>(),
+            );
+        }
+        df.insert(
+            "target",
+            DATA.iter().map(|row| row.label).collect::<Vec<_>>(),
+        );
+        df
+    }
+}
+
+impl Dataset for Digits {
+    type Target = u8;
+
+    const NUM_FEATURES: usize = Self::NUM_FEATURES;
+    const FEATURE_NAMES: &'static [&'static str] = &Self::FEATURE_NAMES;
+    const TARGET_NAMES: &'static [&'static str] = &Self::TARGET_NAMES;
+
+    fn data() -> &'static [Self] {
+        &DATA
+    }
+
+    fn write_features(&self, row: &mut [f32]) {
+        for (value, &pixel) in row.iter_mut().zip(&self.pixels) {
+            *value = f32::from(pixel);
+        }
+    }
+
+    fn target(&self) -> u8 {
+        self.label
+    }
 }
 
 pub const DATA: [Digits; 1797] = [
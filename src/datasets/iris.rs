@@ -1,3 +1,6 @@
+use super::{ClassLabel, Dataset};
+use crate::frame::{Column, DataFrame};
+
 /// [Iris plants dataset](https://github.com/scikit-learn/scikit-learn/blob/dc580a8ef5ee2a8aea80498388690e2213118efd/sklearn/datasets/descr/iris.rst) with 4 numeric, predictive attributes and the class.
 ///
 /// The data set contains 3 classes of 50 instances each, where each class refers to a type of iris plant. One class is linearly separable from the other 2; the latter are NOT linearly separable from each other.
@@ -43,6 +46,21 @@ impl Species {
     }
 }
 
+impl ClassLabel for Species {
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        match index {
+            0 => Self::IrisSetosa,
+            1 => Self::IrisVersicolor,
+            2 => Self::IrisVirginica,
+            _ => panic!("Invalid index"),
+        }
+    }
+}
+
 impl Iris {
     pub const NUM_FEATURES: usize = 4;
     pub const FEATURE_NAMES: [&str; Self::NUM_FEATURES] = [
@@ -56,6 +74,44 @@ impl Iris {
         Species::IrisVersicolor.to_str(),
         Species::IrisVirginica.to_str(),
     ];
+
+    /// Returns the dataset as a frame of the features and a categorical `target` column.
+    pub fn get_as_frame() -> DataFrame {
+        let mut df = DataFrame::from_features(DATA);
+        df.insert(
+            "target",
+            Column::label(
+                &Self::TARGET_NAMES,
+                DATA.iter().map(|row| row.species as usize).collect(),
+            ),
+        );
+        df
+    }
+}
+
+impl Dataset for Iris {
+    type Target = Species;
+
+    const NUM_FEATURES: usize = Self::NUM_FEATURES;
+    const FEATURE_NAMES: &'static [&'static str] = &Self::FEATURE_NAMES;
+    const TARGET_NAMES: &'static [&'static str] = &Self::TARGET_NAMES;
+
+    fn data() -> &'static [Self] {
+        DATA
+    }
+
+    fn write_features(&self, row: &mut [f32]) {
+        row.copy_from_slice(&[
+            self.sepal_length,
+            self.sepal_width,
+            self.petal_length,
+            self.petal_width,
+        ]);
+    }
+
+    fn target(&self) -> Species {
+        self.species
+    }
 }
 
 macro_rules! iris_row {
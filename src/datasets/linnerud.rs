@@ -1,3 +1,6 @@
+use super::Dataset;
+use crate::frame::DataFrame;
+
 /// [Linnerud dataset](https://github.com/scikit-learn/scikit-learn/blob/dc580a8ef5ee2a8aea80498388690e2213118efd/sklearn/datasets/descr/linnerud.rst) with 20 samples and 3 features.
 ///
 /// The Linnerud dataset is a multi-output regression dataset. It consists of three exercise (data) and three physiological (target) variables collected from twenty middle-aged men in a fitness club.
@@ -23,9 +26,43 @@ pub struct Linnerud {
 }
 
 impl Linnerud {
-    pub const NUM_FEATURES: usize = 6;
-    pub const FEATURE_NAMES: [&str; Self::NUM_FEATURES] =
-        ["weight", "waist", "pulse", "chins", "situps", "jumps"];
+    pub const NUM_FEATURES: usize = 3;
+    pub const FEATURE_NAMES: [&str; Self::NUM_FEATURES] = ["chins", "situps", "jumps"];
+    pub const NUM_TARGETS: usize = 3;
+    pub const TARGET_NAMES: [&str; Self::NUM_TARGETS] = ["weight", "waist", "pulse"];
+
+    /// Returns the dataset as a frame of `i32` columns, the features followed by the targets.
+    pub fn get_as_frame() -> DataFrame {
+        let mut df = DataFrame::new();
+        df.insert("chins", DATA.map(|row| row.chins).to_vec());
+        df.insert("situps", DATA.map(|row| row.situps).to_vec());
+        df.insert("jumps", DATA.map(|row| row.jumps).to_vec());
+        df.insert("weight", DATA.map(|row| row.weight).to_vec());
+        df.insert("waist", DATA.map(|row| row.waist).to_vec());
+        df.insert("pulse", DATA.map(|row| row.pulse).to_vec());
+        df
+    }
+}
+
+impl Dataset for Linnerud {
+    /// The three physiological variables, in `TARGET_NAMES` order.
+    type Target = [i32; Self::NUM_TARGETS];
+
+    const NUM_FEATURES: usize = Self::NUM_FEATURES;
+    const FEATURE_NAMES: &'static [&'static str] = &Self::FEATURE_NAMES;
+    const TARGET_NAMES: &'static [&'static str] = &Self::TARGET_NAMES;
+
+    fn data() -> &'static [Self] {
+        &DATA
+    }
+
+    fn write_features(&self, row: &mut [f32]) {
+        row.copy_from_slice(&[self.chins as f32, self.situps as f32, self.jumps as f32]);
+    }
+
+    fn target(&self) -> Self::Target {
+        [self.weight, self.waist, self.pulse]
+    }
 }
 
 macro_rules! linnerud_row {
@@ -1,6 +1,11 @@
 // Some values in these datasets are flagged as similar to defined f32 constants.
 // This directive makes clippy ignore these false positives.
 #![allow(clippy::approx_constant)]
+// The tables below are copied verbatim from their sources, so their size and the precision of a
+// few of their literals are expected.
+#![allow(clippy::large_const_arrays, clippy::excessive_precision)]
+
+use crate::matrix::Matrix;
 
 pub mod breast_cancer;
 pub mod diabetes;
@@ -8,3 +13,107 @@ pub mod digits;
 pub mod iris;
 pub mod linnerud;
 pub mod wine;
+
+/// A record of one of the embedded datasets.
+///
+/// Every dataset module exposes its samples as a `DATA` array of records. Implementing this trait
+/// for the record type lets generic code (training loops, reports, ...) work with any of them.
+pub trait Dataset: Copy + 'static {
+    /// Type of the value each record is labelled with.
+    type Target: Copy + PartialEq;
+
+    /// Number of features of each record.
+    const NUM_FEATURES: usize;
+
+    /// Names of the features, in the order they are extracted by [Dataset::write_features].
+    const FEATURE_NAMES: &'static [&'static str];
+
+    /// Names of the target classes, or of the target columns for regression datasets.
+    const TARGET_NAMES: &'static [&'static str];
+
+    /// All the samples of the dataset.
+    fn data() -> &'static [Self];
+
+    /// Number of samples in the dataset.
+    fn num_samples() -> usize {
+        Self::data().len()
+    }
+
+    /// Writes the features of this record into `row`, which must hold [Dataset::NUM_FEATURES]
+    /// values.
+    fn write_features(&self, row: &mut [f32]);
+
+    /// Returns the features of this record.
+    fn features(&self) -> Vec<f32> {
+        let mut row = vec![0.0; Self::NUM_FEATURES];
+        self.write_features(&mut row);
+        row
+    }
+
+    /// Returns the target of this record.
+    fn target(&self) -> Self::Target;
+
+    /// Returns the features of `records` as a matrix with one row per record.
+    fn to_matrix(records: &[Self]) -> Matrix {
+        let mut matrix = Matrix::zeros(records.len(), Self::NUM_FEATURES);
+        for (i, record) in records.iter().enumerate() {
+            record.write_features(matrix.row_mut(i));
+        }
+        matrix
+    }
+
+    /// Returns the features of `records` as a matrix, and their targets.
+    fn to_xy(records: &[Self]) -> (Matrix, Vec<Self::Target>) {
+        (
+            Self::to_matrix(records),
+            records.iter().map(Self::target).collect(),
+        )
+    }
+}
+
+/// A target that is one of a fixed set of classes.
+pub trait ClassLabel: Copy + Eq {
+    /// Position of the class, which is also the position of its name in the `TARGET_NAMES` of
+    /// its dataset.
+    fn index(&self) -> usize;
+
+    /// Returns the class at position `index`.
+    fn from_index(index: usize) -> Self;
+}
+
+/// Digit labels are their own index.
+impl ClassLabel for u8 {
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        u8::try_from(index).expect("Invalid index")
+    }
+}
+
+#[test]
+fn check_shapes() {
+    fn check<T: Dataset>(num_samples: usize) {
+        assert_eq!(T::num_samples(), num_samples);
+        assert_eq!(T::FEATURE_NAMES.len(), T::NUM_FEATURES);
+        for record in T::data() {
+            assert_eq!(record.features().len(), T::NUM_FEATURES);
+        }
+
+        let (x, y) = T::to_xy(T::data());
+        assert_eq!(x.shape(), (num_samples, T::NUM_FEATURES));
+        assert_eq!(y.len(), num_samples);
+        assert_eq!(
+            x.row(num_samples - 1),
+            T::data()[num_samples - 1].features()
+        );
+    }
+
+    check::<breast_cancer::BreastCancer>(569);
+    check::<diabetes::Diabetes>(442);
+    check::<digits::Digits>(1797);
+    check::<iris::Iris>(150);
+    check::<linnerud::Linnerud>(20);
+    check::<wine::Wine>(178);
+}
@@ -1,3 +1,6 @@
+use super::{ClassLabel, Dataset};
+use crate::frame::{Column, DataFrame};
+
 /// [Wine recognition dataset](https://github.com/scikit-learn/scikit-learn/blob/dc580a8ef5ee2a8aea80498388690e2213118efd/sklearn/datasets/descr/wine_data.rst) with 13 numeric, predictive attributes and the class.
 ///
 /// The data is the results of a chemical analysis of wines grown in the same region in Italy by three different cultivators. There are thirteen different measurements taken for different constituents found in the three types of wine.
@@ -63,6 +66,21 @@ impl Label {
     }
 }
 
+impl ClassLabel for Label {
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        match index {
+            0 => Self::Class0,
+            1 => Self::Class1,
+            2 => Self::Class2,
+            _ => panic!("Invalid index"),
+        }
+    }
+}
+
 impl Wine {
     pub const NUM_FEATURES: usize = 13;
     pub const FEATURE_NAMES: [&str; Self::NUM_FEATURES] = [
@@ -85,6 +103,53 @@ impl Wine {
         Label::Class1.to_str(),
         Label::Class2.to_str(),
     ];
+
+    /// Returns the dataset as a frame of the features and a categorical `target` column.
+    pub fn get_as_frame() -> DataFrame {
+        let mut df = DataFrame::from_features(&DATA);
+        df.insert(
+            "target",
+            Column::label(
+                &Self::TARGET_NAMES,
+                DATA.iter().map(|row| row.label as usize).collect(),
+            ),
+        );
+        df
+    }
+}
+
+impl Dataset for Wine {
+    type Target = Label;
+
+    const NUM_FEATURES: usize = Self::NUM_FEATURES;
+    const FEATURE_NAMES: &'static [&'static str] = &Self::FEATURE_NAMES;
+    const TARGET_NAMES: &'static [&'static str] = &Self::TARGET_NAMES;
+
+    fn data() -> &'static [Self] {
+        &DATA
+    }
+
+    fn write_features(&self, row: &mut [f32]) {
+        row.copy_from_slice(&[
+            self.alcohol,
+            self.malic_acid,
+            self.ash,
+            self.alcalinity_of_ash,
+            self.magnesium,
+            self.total_phenols,
+            self.flavanoids,
+            self.nonflavanoid_phenols,
+            self.proanthocyanins,
+            self.color_intensity,
+            self.hue,
+            self.od280_od315_of_diluted_wines,
+            self.proline,
+        ]);
+    }
+
+    fn target(&self) -> Label {
+        self.label
+    }
 }
 
 macro_rules! wine_row {
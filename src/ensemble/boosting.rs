@@ -0,0 +1,714 @@
+use std::marker::PhantomData;
+
+use super::histogram::{grow_tree, Bins};
+use super::{argmax, mean_importances};
+use crate::datasets::ClassLabel;
+use crate::matrix::Matrix;
+use crate::tree::{tree_params_builders, Tree, TreeParams};
+use crate::utils::random::Rng;
+
+/// Loss minimized by a [GradientBoostingRegressor].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RegressionLoss {
+    /// Half the squared error (the default).
+    #[default]
+    SquaredError,
+
+    /// Absolute error, which predicts conditional medians and is robust to outliers.
+    AbsoluteError,
+
+    /// Squared error for residuals up to the given quantile (`0.0..1.0`, e.g. 0.9) of the
+    /// absolute residuals, and absolute error beyond it.
+    Huber(f64),
+}
+
+/// Loss of the raw scores predicted by the trees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Loss {
+    Regression(RegressionLoss),
+
+    /// Binary cross-entropy of a single raw score, the log-odds of class 1.
+    Binomial,
+
+    /// Cross-entropy of the softmax of one raw score per class.
+    Multinomial(usize),
+}
+
+/// First and second derivatives of the loss of each sample (rows) with respect to each raw score
+/// (columns).
+struct Derivatives {
+    gradients: Matrix<f64>,
+    hessians: Matrix<f64>,
+
+    /// Residual beyond which the Huber loss is linear.
+    delta: f64,
+}
+
+fn sigmoid(raw: f64) -> f64 {
+    1.0 / (1.0 + (-raw).exp())
+}
+
+fn softmax(raw: &[f64]) -> Vec<f64> {
+    let max = raw.iter().fold(f64::NEG_INFINITY, |max, &v| max.max(v));
+    let exp: Vec<f64> = raw.iter().map(|v| (v - max).exp()).collect();
+    let sum: f64 = exp.iter().sum();
+    exp.iter().map(|v| v / sum).collect()
+}
+
+/// Value below which a fraction `q` of `values` lies (linear interpolation).
+fn quantile(mut values: Vec<f64>, q: f64) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let position = q * (values.len() - 1) as f64;
+    let (below, above) = (position.floor() as usize, position.ceil() as usize);
+    values[below] + (values[above] - values[below]) * (position - below as f64)
+}
+
+impl Loss {
+    fn num_scores(&self) -> usize {
+        match *self {
+            Loss::Multinomial(num_classes) => num_classes,
+            _ => 1,
+        }
+    }
+
+    /// Constant raw scores that minimize the loss on `y`.
+    fn init(&self, y: &[f64]) -> Vec<f64> {
+        let n = y.len() as f64;
+        match *self {
+            Loss::Regression(RegressionLoss::SquaredError) => vec![y.iter().sum::<f64>() / n],
+            Loss::Regression(_) => vec![quantile(y.to_vec(), 0.5)],
+            Loss::Binomial => {
+                let p = (y.iter().sum::<f64>() / n).clamp(1e-12, 1.0 - 1e-12);
+                vec![(p / (1.0 - p)).ln()]
+            }
+            Loss::Multinomial(num_classes) => {
+                let mut counts = vec![0.0; num_classes];
+                y.iter().for_each(|&k| counts[k as usize] += 1.0);
+                counts.iter().map(|c| (c / n).max(1e-12).ln()).collect()
+            }
+        }
+    }
+
+    /// Mean loss of the raw scores (rows of `raw`) predicted for `y`.
+    fn loss(&self, y: &[f64], raw: &Matrix<f64>) -> f64 {
+        let residuals = || y.iter().zip(raw.rows()).map(|(t, raw)| t - raw[0]);
+        let total: f64 = match *self {
+            Loss::Regression(RegressionLoss::SquaredError) => {
+                residuals().map(|r| r * r / 2.0).sum()
+            }
+            Loss::Regression(RegressionLoss::AbsoluteError) => residuals().map(f64::abs).sum(),
+            Loss::Regression(RegressionLoss::Huber(alpha)) => {
+                let delta = quantile(residuals().map(f64::abs).collect(), alpha);
+                residuals()
+                    .map(|r| match r.abs() {
+                        r if r <= delta => r * r / 2.0,
+                        r => delta * (r - delta / 2.0),
+                    })
+                    .sum()
+            }
+            // log(1 + e^raw) - y raw, computed without overflow.
+            Loss::Binomial => y
+                .iter()
+                .zip(raw.rows())
+                .map(|(t, raw)| raw[0].max(0.0) + (-raw[0].abs()).exp().ln_1p() - t * raw[0])
+                .sum(),
+            Loss::Multinomial(_) => y
+                .iter()
+                .zip(raw.rows())
+                .map(|(&t, raw)| -softmax(raw)[t as usize].max(1e-300).ln())
+                .sum(),
+        };
+        total / y.len() as f64
+    }
+
+    fn derivatives(&self, y: &[f64], raw: &Matrix<f64>, samples: &[usize]) -> Derivatives {
+        let (n, num_scores) = raw.shape();
+        let mut gradients = Matrix::zeros(n, num_scores);
+        let mut hessians = Matrix::filled(n, num_scores, 1.0);
+        let mut delta = 0.0;
+
+        match *self {
+            Loss::Regression(loss) => {
+                if let RegressionLoss::Huber(alpha) = loss {
+                    let residuals = samples.iter().map(|&i| (y[i] - raw[(i, 0)]).abs());
+                    delta = quantile(residuals.collect(), alpha);
+                }
+                for &i in samples {
+                    let residual = y[i] - raw[(i, 0)];
+                    gradients[(i, 0)] = match loss {
+                        RegressionLoss::SquaredError => -residual,
+                        RegressionLoss::AbsoluteError if residual == 0.0 => 0.0,
+                        RegressionLoss::AbsoluteError => -residual.signum(),
+                        RegressionLoss::Huber(_) => -residual.clamp(-delta, delta),
+                    };
+                }
+            }
+            Loss::Binomial => {
+                for &i in samples {
+                    let p = sigmoid(raw[(i, 0)]);
+                    gradients[(i, 0)] = p - y[i];
+                    hessians[(i, 0)] = (p * (1.0 - p)).max(1e-16);
+                }
+            }
+            Loss::Multinomial(_) => {
+                for &i in samples {
+                    for (k, p) in softmax(raw.row(i)).into_iter().enumerate() {
+                        let target = if y[i] as usize == k { 1.0 } else { 0.0 };
+                        gradients[(i, k)] = p - target;
+                        hessians[(i, k)] = (p * (1.0 - p)).max(1e-16);
+                    }
+                }
+            }
+        }
+        Derivatives {
+            gradients,
+            hessians,
+            delta,
+        }
+    }
+
+    /// Replaces the Newton steps in the leaves of `tree`, fitted to the derivatives of raw score
+    /// `k`, by a step that better suits the loss.
+    fn update_leaves(
+        &self,
+        tree: &mut Tree,
+        x: &Matrix,
+        y: &[f64],
+        raw: &Matrix<f64>,
+        samples: &[usize],
+        delta: f64,
+    ) {
+        match *self {
+            Loss::Regression(RegressionLoss::SquaredError) | Loss::Binomial => {}
+            // Friedman's correction for the redundancy of the scores of the classes.
+            Loss::Multinomial(num_classes) => {
+                let factor = (num_classes - 1) as f64 / num_classes as f64;
+                tree.nodes
+                    .iter_mut()
+                    .for_each(|node| node.value[0] *= factor);
+            }
+            // Gradients of the absolute error ignore the size of the residuals, so the leaves
+            // take the median residual of their samples, or Friedman's robust Huber estimate.
+            Loss::Regression(loss) => {
+                let mut residuals = vec![Vec::new(); tree.nodes.len()];
+                for &i in samples {
+                    residuals[tree.leaf(x.row(i))].push(y[i] - raw[(i, 0)]);
+                }
+                for (node, residuals) in tree.nodes.iter_mut().zip(residuals) {
+                    if residuals.is_empty() {
+                        continue;
+                    }
+                    let median = quantile(residuals.clone(), 0.5);
+                    node.value[0] = match loss {
+                        RegressionLoss::Huber(_) => {
+                            let sum: f64 = residuals
+                                .iter()
+                                .map(|r| (r - median).clamp(-delta, delta))
+                                .sum();
+                            median + sum / residuals.len() as f64
+                        }
+                        _ => median,
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// Early stopping on the loss of a held-out validation set.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct EarlyStopping {
+    validation_fraction: f32,
+    patience: usize,
+}
+
+/// Settings of the boosting, apart from those of the trees.
+#[derive(Clone, Debug, PartialEq)]
+struct BoostingParams {
+    num_iter: usize,
+    learning_rate: f64,
+    subsample: f32,
+    l2_regularization: f64,
+    max_bins: usize,
+    early_stopping: Option<EarlyStopping>,
+    tol: f64,
+    seed: u64,
+}
+
+impl Default for BoostingParams {
+    fn default() -> Self {
+        Self {
+            num_iter: 100,
+            learning_rate: 0.1,
+            subsample: 1.0,
+            l2_regularization: 0.0,
+            max_bins: 256,
+            early_stopping: None,
+            tol: 1e-7,
+            seed: 0,
+        }
+    }
+}
+
+fn default_tree_params() -> TreeParams {
+    TreeParams {
+        max_depth: Some(3),
+        ..TreeParams::default()
+    }
+}
+
+/// A fitted sequence of trees, each predicting a correction of the raw scores of the previous
+/// ones.
+#[derive(Clone, Debug, PartialEq)]
+struct Boosted {
+    init: Vec<f64>,
+
+    /// The trees of each iteration, one per raw score.
+    trees: Vec<Vec<Tree>>,
+
+    /// Validation loss after each iteration, when stopping early.
+    validation_losses: Option<Vec<f64>>,
+    num_features: usize,
+}
+
+impl Boosted {
+    /// Boosts trees on the rows of `x` and their targets `y` (class indices for classifiers).
+    fn fit(
+        x: &Matrix,
+        y: &[f64],
+        loss: Loss,
+        tree_params: &TreeParams,
+        params: &BoostingParams,
+    ) -> Self {
+        assert_eq!(x.num_rows(), y.len(), "Inconsistent number of samples");
+        assert!(!y.is_empty(), "No samples");
+        let mut rng = Rng::new(params.seed);
+
+        let (x, y, validation) = match params.early_stopping {
+            Some(stopping) => {
+                let n = y.len();
+                let num_validation = ((stopping.validation_fraction * n as f32).ceil() as usize)
+                    .clamp(1, n.saturating_sub(1).max(1));
+                let mut indices: Vec<usize> = (0..n).collect();
+                rng.shuffle(&mut indices);
+                let (validation, train) = indices.split_at(num_validation);
+                let pick =
+                    |indices: &[usize]| -> Vec<f64> { indices.iter().map(|&i| y[i]).collect() };
+                (
+                    x.select_rows(train),
+                    pick(train),
+                    Some((x.select_rows(validation), pick(validation))),
+                )
+            }
+            None => (x.clone(), y.to_vec(), None),
+        };
+        let n = y.len();
+        let bins = Bins::new(&x, params.max_bins);
+
+        let init = loss.init(&y);
+        let initial_raw = |rows: usize| Matrix::new(rows, init.len(), init.repeat(rows));
+        let mut raw = initial_raw(n);
+        let mut validation = validation.map(|(x, y)| {
+            let raw = initial_raw(x.num_rows());
+            (x, y, raw)
+        });
+
+        let mut boosted = Self {
+            init: init.clone(),
+            trees: Vec::new(),
+            validation_losses: validation.as_ref().map(|_| Vec::new()),
+            num_features: x.num_cols(),
+        };
+        let num_samples = ((params.subsample * n as f32) as usize).max(1);
+        let mut best = (f64::INFINITY, 0);
+
+        for iter in 0..params.num_iter {
+            let samples = if num_samples < n {
+                rng.sample(n, num_samples)
+            } else {
+                (0..n).collect()
+            };
+            let derivatives = loss.derivatives(&y, &raw, &samples);
+
+            let mut trees = Vec::with_capacity(loss.num_scores());
+            for k in 0..loss.num_scores() {
+                let gradients: Vec<f64> = derivatives.gradients.col(k).copied().collect();
+                let hessians: Vec<f64> = derivatives.hessians.col(k).copied().collect();
+                let mut tree = grow_tree(
+                    &bins,
+                    &gradients,
+                    &hessians,
+                    samples.clone(),
+                    tree_params,
+                    params.l2_regularization,
+                    &mut rng,
+                );
+                loss.update_leaves(&mut tree, &x, &y, &raw, &samples, derivatives.delta);
+                for node in &mut tree.nodes {
+                    node.value[0] *= params.learning_rate;
+                }
+                trees.push(tree);
+            }
+
+            // The scores of every class must be updated only once all their trees are grown.
+            for (k, tree) in trees.iter().enumerate() {
+                for i in 0..n {
+                    raw[(i, k)] += tree.value(x.row(i))[0];
+                }
+                if let Some((x, _, raw)) = validation.as_mut() {
+                    for i in 0..x.num_rows() {
+                        raw[(i, k)] += tree.value(x.row(i))[0];
+                    }
+                }
+            }
+            boosted.trees.push(trees);
+
+            if let (Some((_, y, raw)), Some(stopping)) = (&validation, params.early_stopping) {
+                let loss = loss.loss(y, raw);
+                boosted.validation_losses.as_mut().unwrap().push(loss);
+                if loss < best.0 - params.tol {
+                    best = (loss, iter + 1);
+                } else if iter + 1 - best.1 >= stopping.patience {
+                    // Keep the trees up to the best validation loss.
+                    boosted.trees.truncate(best.1.max(1));
+                    break;
+                }
+            }
+        }
+        boosted
+    }
+
+    /// Raw scores of `row`.
+    fn raw(&self, row: &[f32]) -> Vec<f64> {
+        let mut raw = self.init.clone();
+        for trees in &self.trees {
+            for (score, tree) in raw.iter_mut().zip(trees) {
+                *score += tree.value(row)[0];
+            }
+        }
+        raw
+    }
+
+    fn importances(&self) -> Vec<f64> {
+        mean_importances(self.trees.iter().flatten(), self.num_features)
+    }
+}
+
+/// Builder methods for the [BoostingParams] in the `boosting` field of an estimator.
+macro_rules! boosting_params_builders {
+    () => {
+        /// Maximum number of boosting iterations (100 by default).
+        pub fn with_num_iter(mut self, num_iter: usize) -> Self {
+            self.boosting.num_iter = num_iter;
+            self
+        }
+
+        /// Shrinkage of the prediction of each tree (0.1 by default).
+        pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+            assert!(learning_rate > 0.0, "Invalid learning rate");
+            self.boosting.learning_rate = learning_rate;
+            self
+        }
+
+        /// Fraction (`(0.0, 1.0]`) of the training samples drawn without replacement for each
+        /// iteration (all by default), which makes stochastic gradient boosting.
+        pub fn with_subsample(mut self, subsample: f32) -> Self {
+            assert!(
+                subsample > 0.0 && subsample <= 1.0,
+                "Invalid subsample fraction"
+            );
+            self.boosting.subsample = subsample;
+            self
+        }
+
+        /// L2 penalty on the leaf values (0 by default).
+        pub fn with_l2_regularization(mut self, l2_regularization: f64) -> Self {
+            self.boosting.l2_regularization = l2_regularization;
+            self
+        }
+
+        /// Maximum number of bins (`2..=256`, 256 by default) each feature is discretized into
+        /// before looking for splits.
+        pub fn with_max_bins(mut self, max_bins: usize) -> Self {
+            assert!((2..=256).contains(&max_bins), "Invalid number of bins");
+            self.boosting.max_bins = max_bins;
+            self
+        }
+
+        /// Holds out `validation_fraction` of the training samples, and stops once the loss on
+        /// them has not improved for `patience` iterations, keeping the trees up to the best
+        /// iteration.
+        pub fn with_early_stopping(mut self, validation_fraction: f32, patience: usize) -> Self {
+            assert!(
+                validation_fraction > 0.0 && validation_fraction < 1.0,
+                "Invalid validation fraction"
+            );
+            assert!(patience > 0, "Invalid patience");
+            self.boosting.early_stopping = Some(EarlyStopping {
+                validation_fraction,
+                patience,
+            });
+            self
+        }
+
+        /// Smallest decrease of the validation loss that counts as an improvement, when stopping
+        /// early (1e-7 by default).
+        pub fn with_tol(mut self, tol: f64) -> Self {
+            assert!(tol >= 0.0, "Invalid tolerance");
+            self.boosting.tol = tol;
+            self
+        }
+
+        /// Seed of the subsamples, of the validation set and of the draw of features.
+        pub fn with_seed(mut self, seed: u64) -> Self {
+            self.boosting.seed = seed;
+            self
+        }
+
+        /// Number of boosting iterations kept, once fitted.
+        pub fn num_iter(&self) -> Option<usize> {
+            self.fitted.as_ref().map(|fitted| fitted.trees.len())
+        }
+
+        /// Loss on the validation set after each iteration, once fitted with early stopping.
+        pub fn validation_losses(&self) -> Option<&[f64]> {
+            self.fitted.as_ref()?.validation_losses.as_deref()
+        }
+
+        /// Mean impurity-based importance of each feature over the trees, normalized to sum to
+        /// 1, once fitted.
+        pub fn feature_importances(&self) -> Option<Vec<f64>> {
+            self.fitted.as_ref().map(Boosted::importances)
+        }
+
+        /// Pairs each feature name, typically from the `FEATURE_NAMES` of the dataset, with its
+        /// importance, once fitted.
+        pub fn named_importances<'a>(
+            &self,
+            feature_names: &[&'a str],
+        ) -> Option<Vec<(&'a str, f64)>> {
+            Some(crate::tree::name_importances(
+                self.feature_importances()?,
+                feature_names,
+            ))
+        }
+    };
+}
+
+/// Gradient boosted trees regressor: each tree fits the negative gradient of the loss of the
+/// previous ones, with splits found on histograms of the binned features.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GradientBoostingRegressor {
+    loss: RegressionLoss,
+    params: TreeParams,
+    boosting: BoostingParams,
+    fitted: Option<Boosted>,
+}
+
+impl GradientBoostingRegressor {
+    /// 100 trees of depth 3 minimizing the [squared error](RegressionLoss::SquaredError), with
+    /// a learning rate of 0.1.
+    pub fn new() -> Self {
+        Self {
+            loss: RegressionLoss::SquaredError,
+            params: default_tree_params(),
+            boosting: BoostingParams::default(),
+            fitted: None,
+        }
+    }
+
+    /// Loss to minimize ([squared error](RegressionLoss::SquaredError) by default). The
+    /// quantile of a [Huber](RegressionLoss::Huber) loss must be strictly between 0 and 1.
+    pub fn with_loss(mut self, loss: RegressionLoss) -> Self {
+        if let RegressionLoss::Huber(alpha) = loss {
+            assert!(alpha > 0.0 && alpha < 1.0, "Invalid quantile");
+        }
+        self.loss = loss;
+        self
+    }
+
+    tree_params_builders!();
+    boosting_params_builders!();
+
+    /// Fits the trees to the rows of `x` and their targets `y`.
+    pub fn fit<Y: Copy + Into<f64>>(&mut self, x: &Matrix, y: &[Y]) {
+        let targets: Vec<f64> = y.iter().map(|&v| v.into()).collect();
+        let loss = Loss::Regression(self.loss);
+        self.fitted = Some(Boosted::fit(
+            x,
+            &targets,
+            loss,
+            &self.params,
+            &self.boosting,
+        ));
+    }
+
+    /// Predicts the target of each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<f32> {
+        let boosted = self.fitted.as_ref().expect("Model not fitted");
+        x.rows().map(|row| boosted.raw(row)[0] as f32).collect()
+    }
+}
+
+impl Default for GradientBoostingRegressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gradient boosted trees classifier, minimizing the log-loss of two classes, or the
+/// multinomial deviance of more with one tree per class and iteration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GradientBoostingClassifier<L> {
+    params: TreeParams,
+    boosting: BoostingParams,
+    fitted: Option<Boosted>,
+    labels: PhantomData<L>,
+}
+
+impl<L: ClassLabel> GradientBoostingClassifier<L> {
+    /// 100 iterations of trees of depth 3, with a learning rate of 0.1.
+    pub fn new() -> Self {
+        Self {
+            params: default_tree_params(),
+            boosting: BoostingParams::default(),
+            fitted: None,
+            labels: PhantomData,
+        }
+    }
+
+    tree_params_builders!();
+    boosting_params_builders!();
+
+    /// Fits the trees to the rows of `x` and their labels `y`.
+    ///
+    /// The classes are the labels up to the largest [ClassLabel::index] in `y`.
+    pub fn fit(&mut self, x: &Matrix, y: &[L]) {
+        let classes: Vec<f64> = y.iter().map(|label| label.index() as f64).collect();
+        let num_classes = y.iter().map(|label| label.index() + 1).max().unwrap_or(0);
+        let loss = if num_classes <= 2 {
+            Loss::Binomial
+        } else {
+            Loss::Multinomial(num_classes)
+        };
+        self.fitted = Some(Boosted::fit(
+            x,
+            &classes,
+            loss,
+            &self.params,
+            &self.boosting,
+        ));
+    }
+
+    /// Returns the probability of each class (columns, in [ClassLabel::index] order) for each
+    /// row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict_proba(&self, x: &Matrix) -> Matrix<f64> {
+        let boosted = self.fitted.as_ref().expect("Model not fitted");
+        let rows: Vec<Vec<f64>> = x
+            .rows()
+            .map(|row| match boosted.raw(row)[..] {
+                [raw] => {
+                    let p = sigmoid(raw);
+                    vec![1.0 - p, p]
+                }
+                ref raw => softmax(raw),
+            })
+            .collect();
+        Matrix::from_rows(&rows)
+    }
+
+    /// Predicts the most probable class of each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<L> {
+        let probabilities = self.predict_proba(x);
+        probabilities
+            .rows()
+            .map(|row| L::from_index(argmax(row)))
+            .collect()
+    }
+}
+
+impl<L: ClassLabel> Default for GradientBoostingClassifier<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn regressor_losses_on_diabetes() {
+    use crate::datasets::diabetes::{self, Diabetes};
+    use crate::datasets::Dataset;
+    use crate::metrics::regression::r2_score;
+    use crate::model_selection::train_test_split;
+
+    let (train, test) = train_test_split(&diabetes::DATA, 0.25, 0, false);
+    let (x_train, y_train) = Diabetes::to_xy(&train);
+    let (x_test, y_test) = Diabetes::to_xy(&test);
+
+    for loss in [
+        RegressionLoss::SquaredError,
+        RegressionLoss::AbsoluteError,
+        RegressionLoss::Huber(0.9),
+    ] {
+        let mut model = GradientBoostingRegressor::new()
+            .with_loss(loss)
+            .with_learning_rate(0.05)
+            .with_subsample(0.8)
+            .with_min_samples_leaf(5);
+        model.fit(&x_train, &y_train);
+        let r2 = r2_score(&y_test, &model.predict(&x_test));
+        assert!(r2 > 0.35, "{loss:?}: {r2}");
+    }
+
+    // Training too long overfits, which early stopping detects.
+    let mut stopped = GradientBoostingRegressor::new()
+        .with_num_iter(1000)
+        .with_early_stopping(0.2, 10);
+    stopped.fit(&x_train, &y_train);
+    let num_iter = stopped.num_iter().unwrap();
+    assert!(num_iter < 200, "{num_iter}");
+    assert_eq!(stopped.validation_losses().unwrap().len(), num_iter + 10);
+
+    // A tolerance larger than any improvement keeps the first tree only, whichever builder
+    // comes first.
+    let mut strict = GradientBoostingRegressor::new()
+        .with_tol(1e4)
+        .with_early_stopping(0.2, 10);
+    strict.fit(&x_train, &y_train);
+    assert_eq!(strict.num_iter(), Some(1));
+
+    let mut ranked = stopped.named_importances(&Diabetes::FEATURE_NAMES).unwrap();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    assert!(ranked[..2].iter().any(|(name, _)| name.starts_with("bmi")));
+}
+
+#[test]
+fn classifiers_on_breast_cancer_and_iris() {
+    use crate::datasets::breast_cancer::{self, BreastCancer};
+    use crate::datasets::iris::{self, Iris};
+    use crate::datasets::Dataset;
+    use crate::metrics::classification::accuracy;
+    use crate::model_selection::train_test_split;
+
+    let (train, test) = train_test_split(&breast_cancer::DATA, 0.25, 0, true);
+    let (x_train, y_train) = BreastCancer::to_xy(&train);
+    let (x_test, y_test) = BreastCancer::to_xy(&test);
+    let mut model = GradientBoostingClassifier::new().with_early_stopping(0.1, 10);
+    model.fit(&x_train, &y_train);
+    assert!(accuracy(&y_test, &model.predict(&x_test)) > 0.94);
+    let probabilities = model.predict_proba(&x_test);
+    assert!(probabilities
+        .rows()
+        .all(|row| (row.iter().sum::<f64>() - 1.0).abs() < 1e-9));
+
+    let (x, y) = Iris::to_xy(iris::DATA);
+    let mut model = GradientBoostingClassifier::new().with_num_iter(20);
+    model.fit(&x, &y);
+    assert_eq!(model.num_iter(), Some(20));
+    assert!(accuracy(&y, &model.predict(&x)) > 0.97);
+}
@@ -0,0 +1,460 @@
+use std::marker::PhantomData;
+use std::thread;
+
+use super::{argmax, mean_importances};
+use crate::datasets::ClassLabel;
+use crate::matrix::Matrix;
+use crate::metrics::classification::accuracy;
+use crate::metrics::regression::r2_score;
+use crate::tree::{
+    tree_params_builders, Classes, ClassificationCriterion, Criterion, MaxFeatures,
+    RegressionCriterion, Splitter, Targets, Tree, TreeParams,
+};
+use crate::utils::random::Rng;
+
+/// Settings of a forest, apart from those of its trees.
+#[derive(Clone, Debug, PartialEq)]
+struct ForestParams {
+    num_trees: usize,
+    bootstrap: bool,
+    num_threads: usize,
+    seed: u64,
+}
+
+/// The trees of a fitted forest.
+#[derive(Clone, Debug, PartialEq)]
+struct Forest {
+    trees: Vec<Tree>,
+
+    /// Sum of the predictions of the trees for each training sample that was left out of their
+    /// bootstrap sample, and the number of such trees, when bootstrapping.
+    out_of_bag: Option<(Matrix<f64>, Vec<usize>)>,
+}
+
+impl Forest {
+    /// Grows the trees on the rows of `x`, each on a bootstrap sample of the rows if requested.
+    fn grow(
+        x: &Matrix,
+        criterion: &(impl Criterion + Sync),
+        tree_params: &TreeParams,
+        params: &ForestParams,
+    ) -> Self {
+        assert!(params.num_trees > 0, "At least one tree is needed");
+        let n = x.num_rows();
+
+        // Seeds are drawn up front so that the forest does not depend on the number of threads.
+        let mut rng = Rng::new(params.seed);
+        let seeds: Vec<u64> = (0..params.num_trees).map(|_| rng.next_u64()).collect();
+        let grow_tree = |seed: u64| {
+            let mut rng = Rng::new(seed);
+            let samples: Vec<usize> = if params.bootstrap {
+                (0..n).map(|_| rng.below(n)).collect()
+            } else {
+                (0..n).collect()
+            };
+            let mut in_bag = vec![false; n];
+            samples.iter().for_each(|&i| in_bag[i] = true);
+            let tree = Tree::build(x, criterion, samples, tree_params, &mut rng);
+            (tree, in_bag)
+        };
+
+        let chunk_size = params.num_trees.div_ceil(params.num_threads.max(1));
+        let grown: Vec<(Tree, Vec<bool>)> = thread::scope(|scope| {
+            let handles: Vec<_> = seeds
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(|| {
+                        chunk
+                            .iter()
+                            .map(|&seed| grow_tree(seed))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        let out_of_bag = params.bootstrap.then(|| {
+            let width = grown[0].0.nodes[0].value.len();
+            let mut sums = Matrix::zeros(n, width);
+            let mut counts = vec![0; n];
+            for (tree, in_bag) in &grown {
+                for i in (0..n).filter(|&i| !in_bag[i]) {
+                    let value = tree.value(x.row(i));
+                    sums.row_mut(i)
+                        .iter_mut()
+                        .zip(value)
+                        .for_each(|(sum, v)| *sum += v);
+                    counts[i] += 1;
+                }
+            }
+            (sums, counts)
+        });
+
+        Self {
+            trees: grown.into_iter().map(|(tree, _)| tree).collect(),
+            out_of_bag,
+        }
+    }
+
+    /// Mean of the predictions of the trees for `row`.
+    fn value(&self, row: &[f32]) -> Vec<f64> {
+        let mut sum = vec![0.0; self.trees[0].nodes[0].value.len()];
+        for tree in &self.trees {
+            sum.iter_mut()
+                .zip(tree.value(row))
+                .for_each(|(sum, v)| *sum += v);
+        }
+        let num_trees = self.trees.len() as f64;
+        sum.iter().map(|v| v / num_trees).collect()
+    }
+
+    /// Mean of the out-of-bag predictions, for the samples that some tree left out.
+    fn out_of_bag_values(&self) -> Option<Vec<(usize, Vec<f64>)>> {
+        let (sums, counts) = self.out_of_bag.as_ref()?;
+        Some(
+            counts
+                .iter()
+                .enumerate()
+                .filter(|(_, &count)| count > 0)
+                .map(|(i, &count)| (i, sums.row(i).iter().map(|v| v / count as f64).collect()))
+                .collect(),
+        )
+    }
+
+    fn importances(&self) -> Vec<f64> {
+        mean_importances(&self.trees, self.trees[0].num_features)
+    }
+}
+
+/// Builder methods for the [ForestParams] in the `forest` field of an estimator.
+macro_rules! forest_params_builders {
+    () => {
+        /// Number of trees (100 by default).
+        pub fn with_num_trees(mut self, num_trees: usize) -> Self {
+            self.forest.num_trees = num_trees;
+            self
+        }
+
+        /// Whether each tree is grown on a bootstrap sample of the training set, which also
+        /// gives the out-of-bag score.
+        pub fn with_bootstrap(mut self, bootstrap: bool) -> Self {
+            self.forest.bootstrap = bootstrap;
+            self
+        }
+
+        /// Number of threads the trees are grown on (1 by default). The fitted forest is the
+        /// same for any number of threads.
+        pub fn with_num_threads(mut self, num_threads: usize) -> Self {
+            self.forest.num_threads = num_threads.max(1);
+            self
+        }
+
+        /// Seed of the bootstrap samples and of the random choices of the trees.
+        pub fn with_seed(mut self, seed: u64) -> Self {
+            self.forest.seed = seed;
+            self
+        }
+
+        /// Mean impurity-based importance of each feature over the trees, normalized to sum to
+        /// 1, once fitted.
+        pub fn feature_importances(&self) -> Option<Vec<f64>> {
+            self.fitted
+                .as_ref()
+                .map(|fitted| fitted.forest.importances())
+        }
+
+        /// Pairs each feature name, typically from the `FEATURE_NAMES` of the dataset, with its
+        /// importance, once fitted.
+        pub fn named_importances<'a>(
+            &self,
+            feature_names: &[&'a str],
+        ) -> Option<Vec<(&'a str, f64)>> {
+            Some(crate::tree::name_importances(
+                self.feature_importances()?,
+                feature_names,
+            ))
+        }
+
+        /// Score of the forest on the training samples, each predicted only by the trees whose
+        /// bootstrap sample left it out, once fitted with bootstrapping.
+        pub fn oob_score(&self) -> Option<f64> {
+            self.fitted.as_ref().and_then(|fitted| fitted.oob_score)
+        }
+    };
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct FittedForest {
+    forest: Forest,
+
+    /// Accuracy for classifiers, R² for regressors.
+    oob_score: Option<f64>,
+}
+
+macro_rules! forest_classifier {
+    ($(#[$attr:meta])* $name:ident, $splitter:expr, $bootstrap:expr) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct $name<L> {
+            criterion: ClassificationCriterion,
+            params: TreeParams,
+            forest: ForestParams,
+            fitted: Option<FittedForest>,
+            labels: PhantomData<L>,
+        }
+
+        impl<L: ClassLabel> $name<L> {
+            /// 100 fully grown trees with the [Gini](ClassificationCriterion::Gini) criterion,
+            /// each considering the square root of the number of features for each split.
+            pub fn new() -> Self {
+                Self {
+                    criterion: ClassificationCriterion::Gini,
+                    params: TreeParams {
+                        max_features: MaxFeatures::Sqrt,
+                        splitter: $splitter,
+                        ..TreeParams::default()
+                    },
+                    forest: ForestParams {
+                        num_trees: 100,
+                        bootstrap: $bootstrap,
+                        num_threads: 1,
+                        seed: 0,
+                    },
+                    fitted: None,
+                    labels: PhantomData,
+                }
+            }
+
+            pub fn with_criterion(mut self, criterion: ClassificationCriterion) -> Self {
+                self.criterion = criterion;
+                self
+            }
+
+            tree_params_builders!();
+            forest_params_builders!();
+
+            /// Fits the forest to the rows of `x` and their labels `y`.
+            ///
+            /// The classes are the labels up to the largest [ClassLabel::index] in `y`.
+            pub fn fit(&mut self, x: &Matrix, y: &[L]) {
+                assert_eq!(x.num_rows(), y.len(), "Inconsistent number of samples");
+                let classes: Vec<usize> = y.iter().map(ClassLabel::index).collect();
+                let criterion = Classes {
+                    num_classes: classes.iter().max().map_or(0, |&k| k + 1),
+                    classes: &classes,
+                    criterion: self.criterion,
+                };
+                let forest = Forest::grow(x, &criterion, &self.params, &self.forest);
+
+                let oob_score = forest.out_of_bag_values().map(|values| {
+                    let (y_true, y_pred): (Vec<L>, Vec<L>) = values
+                        .iter()
+                        .map(|(i, fractions)| (y[*i], L::from_index(argmax(fractions))))
+                        .unzip();
+                    accuracy(&y_true, &y_pred)
+                });
+                self.fitted = Some(FittedForest { forest, oob_score });
+            }
+
+            fn forest(&self) -> &Forest {
+                &self.fitted.as_ref().expect("Model not fitted").forest
+            }
+
+            /// Returns the mean over the trees of the fraction of each class (columns, in
+            /// [ClassLabel::index] order) in the leaf of each row of `x`.
+            ///
+            /// Panics if the model has not been fitted.
+            pub fn predict_proba(&self, x: &Matrix) -> Matrix<f64> {
+                let rows: Vec<Vec<f64>> = x.rows().map(|row| self.forest().value(row)).collect();
+                Matrix::from_rows(&rows)
+            }
+
+            /// Predicts the most probable class of each row of `x`.
+            ///
+            /// Panics if the model has not been fitted.
+            pub fn predict(&self, x: &Matrix) -> Vec<L> {
+                x.rows()
+                    .map(|row| L::from_index(argmax(&self.forest().value(row))))
+                    .collect()
+            }
+        }
+
+        impl<L: ClassLabel> Default for $name<L> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+macro_rules! forest_regressor {
+    ($(#[$attr:meta])* $name:ident, $splitter:expr, $bootstrap:expr) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct $name {
+            criterion: RegressionCriterion,
+            params: TreeParams,
+            forest: ForestParams,
+            fitted: Option<FittedForest>,
+        }
+
+        impl $name {
+            /// 100 fully grown trees with the [squared error](RegressionCriterion::SquaredError)
+            /// criterion, each considering every feature for each split.
+            pub fn new() -> Self {
+                Self {
+                    criterion: RegressionCriterion::SquaredError,
+                    params: TreeParams {
+                        splitter: $splitter,
+                        ..TreeParams::default()
+                    },
+                    forest: ForestParams {
+                        num_trees: 100,
+                        bootstrap: $bootstrap,
+                        num_threads: 1,
+                        seed: 0,
+                    },
+                    fitted: None,
+                }
+            }
+
+            pub fn with_criterion(mut self, criterion: RegressionCriterion) -> Self {
+                self.criterion = criterion;
+                self
+            }
+
+            tree_params_builders!();
+            forest_params_builders!();
+
+            /// Fits the forest to the rows of `x` and their targets `y`.
+            pub fn fit<Y: Copy + Into<f64>>(&mut self, x: &Matrix, y: &[Y]) {
+                assert_eq!(x.num_rows(), y.len(), "Inconsistent number of samples");
+                let targets: Vec<f64> = y.iter().map(|&v| v.into()).collect();
+                let criterion = Targets {
+                    targets: &targets,
+                    criterion: self.criterion,
+                };
+                let forest = Forest::grow(x, &criterion, &self.params, &self.forest);
+
+                let oob_score = forest.out_of_bag_values().map(|values| {
+                    let (y_true, y_pred): (Vec<f64>, Vec<f64>) =
+                        values.iter().map(|(i, value)| (targets[*i], value[0])).unzip();
+                    r2_score(&y_true, &y_pred)
+                });
+                self.fitted = Some(FittedForest { forest, oob_score });
+            }
+
+            /// Predicts the target of each row of `x` as the mean prediction of the trees.
+            ///
+            /// Panics if the model has not been fitted.
+            pub fn predict(&self, x: &Matrix) -> Vec<f32> {
+                let forest = &self.fitted.as_ref().expect("Model not fitted").forest;
+                x.rows().map(|row| forest.value(row)[0] as f32).collect()
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+forest_classifier!(
+    /// Random forest classifier: trees grown on bootstrap samples, each split considering a
+    /// random subset of the features, whose predicted class fractions are averaged.
+    RandomForestClassifier,
+    Splitter::Best,
+    true
+);
+
+forest_classifier!(
+    /// Extremely randomized trees classifier: like a [RandomForestClassifier], but each
+    /// candidate feature is split at a random threshold, and the trees are grown on the whole
+    /// training set by default.
+    ExtraTreesClassifier,
+    Splitter::Random,
+    false
+);
+
+forest_regressor!(
+    /// Random forest regressor: trees grown on bootstrap samples, whose predictions are
+    /// averaged.
+    RandomForestRegressor,
+    Splitter::Best,
+    true
+);
+
+forest_regressor!(
+    /// Extremely randomized trees regressor: like a [RandomForestRegressor], but each candidate
+    /// feature is split at a random threshold, and the trees are grown on the whole training
+    /// set by default.
+    ExtraTreesRegressor,
+    Splitter::Random,
+    false
+);
+
+#[test]
+fn classifiers_on_breast_cancer_and_wine() {
+    use crate::datasets::breast_cancer::{self, BreastCancer, Label};
+    use crate::datasets::wine::{self, Wine};
+    use crate::datasets::Dataset;
+    use crate::model_selection::train_test_split;
+
+    let (x, y) = BreastCancer::to_xy(&breast_cancer::DATA);
+    let mut forest = RandomForestClassifier::<Label>::new()
+        .with_num_trees(30)
+        .with_seed(1);
+    forest.fit(&x, &y);
+    assert!(forest.oob_score().unwrap() > 0.93);
+    assert_eq!(accuracy(&y, &forest.predict(&x)), 1.0);
+
+    let importances = forest
+        .named_importances(&BreastCancer::FEATURE_NAMES)
+        .unwrap();
+    assert!((importances.iter().map(|(_, v)| v).sum::<f64>() - 1.0).abs() < 1e-9);
+
+    // Threads do not change the forest.
+    let mut threaded = RandomForestClassifier::<Label>::new()
+        .with_num_trees(30)
+        .with_seed(1)
+        .with_num_threads(4);
+    threaded.fit(&x, &y);
+    assert!(threaded.fitted == forest.fitted);
+
+    let (train, test) = train_test_split(&wine::DATA, 0.3, 0, true);
+    let (x_train, y_train) = Wine::to_xy(&train);
+    let (x_test, y_test) = Wine::to_xy(&test);
+    let mut extra = ExtraTreesClassifier::new().with_num_trees(30);
+    extra.fit(&x_train, &y_train);
+    assert!(accuracy(&y_test, &extra.predict(&x_test)) > 0.93);
+    assert_eq!(extra.oob_score(), None);
+}
+
+#[test]
+fn regressors_on_diabetes() {
+    use crate::datasets::diabetes::{self, Diabetes};
+    use crate::datasets::Dataset;
+
+    let (x, y) = Diabetes::to_xy(&diabetes::DATA);
+    let mut forest = RandomForestRegressor::new()
+        .with_num_trees(30)
+        .with_min_samples_leaf(5)
+        .with_num_threads(2);
+    forest.fit(&x, &y);
+    let oob = forest.oob_score().unwrap();
+    assert!(oob > 0.3 && oob < 0.6, "{oob}");
+
+    let mut extra = ExtraTreesRegressor::new()
+        .with_num_trees(30)
+        .with_bootstrap(true)
+        .with_max_features(MaxFeatures::Fraction(0.5));
+    extra.fit(&x, &y);
+    assert!(extra.oob_score().unwrap() > 0.3);
+    assert!(r2_score(&y, &extra.predict(&x)) > 0.8);
+}
@@ -0,0 +1,290 @@
+use crate::matrix::Matrix;
+use crate::tree::{Node, Split, Tree, TreeParams};
+use crate::utils::random::Rng;
+
+/// Features discretized into at most 256 bins each, so that the best split of a node can be
+/// found from per-bin sums instead of sorting its samples.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Bins {
+    /// Bin of each sample (row) and feature (column).
+    codes: Matrix<u8>,
+
+    /// Upper bound of every bin but the last of each feature: a value falls into the first bin
+    /// whose threshold it does not exceed.
+    thresholds: Vec<Vec<f32>>,
+}
+
+impl Bins {
+    /// Bins each feature of `x` at the midpoints between its distinct values, or between its
+    /// quantiles when it has more than `max_bins` of them.
+    pub(super) fn new(x: &Matrix, max_bins: usize) -> Self {
+        assert!((2..=256).contains(&max_bins), "Invalid number of bins");
+        let thresholds: Vec<Vec<f32>> = (0..x.num_cols())
+            .map(|j| {
+                let mut values: Vec<f32> = x.col(j).copied().collect();
+                values.sort_by(f32::total_cmp);
+                feature_thresholds(&values, max_bins)
+            })
+            .collect();
+
+        let codes = Matrix::new(
+            x.num_rows(),
+            x.num_cols(),
+            x.rows()
+                .flat_map(|row| {
+                    row.iter()
+                        .zip(&thresholds)
+                        .map(|(&v, thresholds)| thresholds.partition_point(|&t| t < v) as u8)
+                })
+                .collect(),
+        );
+        Self { codes, thresholds }
+    }
+}
+
+/// Thresholds between the bins of a feature whose `sorted` values hold at most `max_bins`
+/// samples each, unless a single value is more frequent than that.
+fn feature_thresholds(sorted: &[f32], max_bins: usize) -> Vec<f32> {
+    let mut distinct: Vec<(f32, usize)> = Vec::new();
+    for &v in sorted {
+        match distinct.last_mut() {
+            Some((last, count)) if *last == v => *count += 1,
+            _ => distinct.push((v, 1)),
+        }
+    }
+
+    let mut thresholds = Vec::new();
+    let mut seen = 0;
+    let mut bin = 1;
+    for pair in distinct.windows(2) {
+        let ((below, count), (above, _)) = (pair[0], pair[1]);
+        seen += count;
+        let cut = if distinct.len() <= max_bins {
+            true
+        } else if seen * max_bins >= bin * sorted.len() {
+            // Skip the quantiles this value already covers.
+            while seen * max_bins >= bin * sorted.len() {
+                bin += 1;
+            }
+            true
+        } else {
+            false
+        };
+        if cut && thresholds.len() + 1 < max_bins {
+            // Midpoint, unless rounding puts it on the value above.
+            let middle = below + (above - below) / 2.0;
+            thresholds.push(if middle < above { middle } else { below });
+        }
+    }
+    thresholds
+}
+
+/// Sum of the gradients and hessians of the samples in a bin or node.
+#[derive(Clone, Copy, Debug, Default)]
+struct Sums {
+    gradient: f64,
+    hessian: f64,
+    count: usize,
+}
+
+impl Sums {
+    fn add(&mut self, other: Sums) {
+        self.gradient += other.gradient;
+        self.hessian += other.hessian;
+        self.count += other.count;
+    }
+
+    /// Decrease of the second-order approximation of the loss when the samples are predicted by
+    /// the Newton step `-gradient / (hessian + l2)` rather than 0.
+    fn score(&self, l2: f64) -> f64 {
+        let denominator = self.hessian + l2;
+        if denominator > 0.0 {
+            self.gradient * self.gradient / denominator
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Grows a regression tree on the binned rows listed in `samples`, whose leaves hold the Newton
+/// step `-gradient / (hessian + l2)` of the loss.
+///
+/// The impurity of each node is minus its [score](Sums::score) per sample, so the impurity
+/// decreases that [Tree::importances] adds up are the gains of the splits.
+pub(super) fn grow_tree(
+    bins: &Bins,
+    gradients: &[f64],
+    hessians: &[f64],
+    samples: Vec<usize>,
+    params: &TreeParams,
+    l2: f64,
+    rng: &mut Rng,
+) -> Tree {
+    assert!(!samples.is_empty(), "No samples");
+    let mut grower = Grower {
+        bins,
+        gradients,
+        hessians,
+        params,
+        l2,
+        rng,
+        nodes: Vec::new(),
+    };
+    grower.grow(samples, 0);
+    Tree {
+        nodes: grower.nodes,
+        num_features: bins.codes.num_cols(),
+    }
+}
+
+struct Grower<'a> {
+    bins: &'a Bins,
+    gradients: &'a [f64],
+    hessians: &'a [f64],
+    params: &'a TreeParams,
+    l2: f64,
+    rng: &'a mut Rng,
+    nodes: Vec<Node>,
+}
+
+/// Best split found so far for a node.
+struct Candidate {
+    feature: usize,
+    bin: usize,
+    gain: f64,
+}
+
+impl Grower<'_> {
+    fn sums(&self, i: usize) -> Sums {
+        Sums {
+            gradient: self.gradients[i],
+            hessian: self.hessians[i],
+            count: 1,
+        }
+    }
+
+    /// Adds the node holding `samples` and its descendants, and returns its position.
+    fn grow(&mut self, samples: Vec<usize>, depth: usize) -> usize {
+        let mut total = Sums::default();
+        samples.iter().for_each(|&i| total.add(self.sums(i)));
+        let value = -total.gradient / (total.hessian + self.l2);
+
+        let node = self.nodes.len();
+        self.nodes.push(Node {
+            value: vec![if value.is_finite() { value } else { 0.0 }],
+            impurity: -total.score(self.l2) / samples.len() as f64,
+            num_samples: samples.len(),
+            split: None,
+        });
+
+        let can_split = self.params.max_depth.is_none_or(|max| depth < max)
+            && samples.len() >= self.params.min_samples_split
+            && samples.len() >= 2 * self.params.min_samples_leaf;
+        if !can_split {
+            return node;
+        }
+        let Some(best) = self.find_split(&samples, total) else {
+            return node;
+        };
+
+        let (left, right): (Vec<usize>, Vec<usize>) = samples
+            .into_iter()
+            .partition(|&i| self.bins.codes[(i, best.feature)] as usize <= best.bin);
+        let left = self.grow(left, depth + 1);
+        let right = self.grow(right, depth + 1);
+        self.nodes[node].split = Some(Split {
+            feature: best.feature,
+            threshold: self.bins.thresholds[best.feature][best.bin],
+            left,
+            right,
+        });
+        node
+    }
+
+    /// Finds the split of `samples` with the largest gain, among the features drawn for the
+    /// node.
+    fn find_split(&mut self, samples: &[usize], total: Sums) -> Option<Candidate> {
+        let num_features = self.bins.codes.num_cols();
+        let max_features = self.params.max_features.of(num_features);
+        let min_leaf = self.params.min_samples_leaf;
+
+        let mut features: Vec<usize> = (0..num_features).collect();
+        if max_features < features.len() {
+            self.rng.shuffle(&mut features);
+        }
+
+        let parent = total.score(self.l2);
+        let mut best: Option<Candidate> = None;
+        let mut tried = 0;
+        for feature in features {
+            if tried >= max_features && best.is_some() {
+                break;
+            }
+            let mut histogram = vec![Sums::default(); self.bins.thresholds[feature].len() + 1];
+            for &i in samples {
+                histogram[self.bins.codes[(i, feature)] as usize].add(self.sums(i));
+            }
+            if histogram.iter().filter(|sums| sums.count > 0).count() < 2 {
+                // Constant features cannot split the node, and do not count as tried.
+                continue;
+            }
+            tried += 1;
+
+            let mut left = Sums::default();
+            for (bin, sums) in histogram[..histogram.len() - 1].iter().enumerate() {
+                left.add(*sums);
+                let right = Sums {
+                    gradient: total.gradient - left.gradient,
+                    hessian: total.hessian - left.hessian,
+                    count: total.count - left.count,
+                };
+                if left.count < min_leaf || right.count < min_leaf || sums.count == 0 {
+                    continue;
+                }
+                let gain = left.score(self.l2) + right.score(self.l2) - parent;
+                if gain > 1e-12 && best.as_ref().is_none_or(|best| gain > best.gain) {
+                    best = Some(Candidate { feature, bin, gain });
+                }
+            }
+        }
+        best
+    }
+}
+
+#[test]
+fn bins_and_splits() {
+    let x = Matrix::new(
+        6,
+        2,
+        vec![1.0, 0.0, 1.0, 5.0, 2.0, 0.0, 3.0, 5.0, 3.0, 0.0, 4.0, 5.0],
+    );
+    let bins = Bins::new(&x, 256);
+    assert_eq!(bins.thresholds, [vec![1.5, 2.5, 3.5], vec![2.5]]);
+    assert_eq!(
+        bins.codes.col(0).copied().collect::<Vec<_>>(),
+        [0, 0, 1, 2, 2, 3]
+    );
+
+    // Quantile bins hold about the same number of samples.
+    let x = Matrix::from_column(&(0..1000).map(|v| v as f32).collect::<Vec<_>>());
+    let bins = Bins::new(&x, 4);
+    assert_eq!(bins.thresholds[0], [249.5, 499.5, 749.5]);
+
+    // With unit hessians, the tree fits the mean of minus the gradients.
+    let gradients: Vec<f64> = (0..1000)
+        .map(|v| if v < 500 { -1.0 } else { 2.0 })
+        .collect();
+    let params = TreeParams::default();
+    let tree = grow_tree(
+        &bins,
+        &gradients,
+        &[1.0; 1000],
+        (0..1000).collect(),
+        &params,
+        0.0,
+        &mut Rng::new(0),
+    );
+    assert_eq!(tree.num_leaves(), 2);
+    assert_eq!(tree.value(&[499.0]), [1.0]);
+    assert_eq!(tree.value(&[500.0]), [-2.0]);
+}
@@ -0,0 +1,37 @@
+use crate::tree::Tree;
+
+mod boosting;
+mod forest;
+mod histogram;
+
+pub use boosting::{GradientBoostingClassifier, GradientBoostingRegressor, RegressionLoss};
+pub use forest::{
+    ExtraTreesClassifier, ExtraTreesRegressor, RandomForestClassifier, RandomForestRegressor,
+};
+
+/// Position of the largest value, the first one on ties.
+fn argmax(values: &[f64]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .fold(0, |best, (k, &v)| if v > values[best] { k } else { best })
+}
+
+/// Mean of the impurity-based importances of `trees`, normalized to sum to 1.
+fn mean_importances<'a>(
+    trees: impl IntoIterator<Item = &'a Tree>,
+    num_features: usize,
+) -> Vec<f64> {
+    let mut importances = vec![0.0; num_features];
+    for tree in trees {
+        importances
+            .iter_mut()
+            .zip(tree.importances())
+            .for_each(|(sum, v)| *sum += v);
+    }
+    let total: f64 = importances.iter().sum();
+    if total > 0.0 {
+        importances.iter_mut().for_each(|v| *v /= total);
+    }
+    importances
+}
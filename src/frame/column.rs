@@ -0,0 +1,151 @@
+use std::fmt;
+
+/// A typed column of a [DataFrame](super::DataFrame).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Column {
+    F32(Vec<f32>),
+    I32(Vec<i32>),
+    U8(Vec<u8>),
+
+    /// Categorical values, stored as indices (codes) into a list of category names.
+    Label {
+        categories: Vec<String>,
+        codes: Vec<usize>,
+    },
+}
+
+/// A single cell of a [Column].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value<'a> {
+    F32(f32),
+    I32(i32),
+    U8(u8),
+    Label(&'a str),
+}
+
+impl Column {
+    /// Creates a categorical column from the names of its categories and the code of each value.
+    pub fn label(categories: &[&str], codes: Vec<usize>) -> Self {
+        assert!(
+            codes.iter().all(|&code| code < categories.len()),
+            "Invalid category code"
+        );
+
+        Column::Label {
+            categories: categories.iter().map(|c| c.to_string()).collect(),
+            codes,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Column::F32(values) => values.len(),
+            Column::I32(values) => values.len(),
+            Column::U8(values) => values.len(),
+            Column::Label { codes, .. } => codes.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value at row `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Value<'_>> {
+        match self {
+            Column::F32(values) => values.get(index).map(|&v| Value::F32(v)),
+            Column::I32(values) => values.get(index).map(|&v| Value::I32(v)),
+            Column::U8(values) => values.get(index).map(|&v| Value::U8(v)),
+            Column::Label { categories, codes } => codes
+                .get(index)
+                .map(|&code| Value::Label(&categories[code])),
+        }
+    }
+
+    pub fn as_f32(&self) -> Option<&[f32]> {
+        match self {
+            Column::F32(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(&self) -> Option<&[i32]> {
+        match self {
+            Column::I32(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_u8(&self) -> Option<&[u8]> {
+        match self {
+            Column::U8(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the category names and codes of a categorical column.
+    pub fn as_label(&self) -> Option<(&[String], &[usize])> {
+        match self {
+            Column::Label { categories, codes } => Some((categories, codes)),
+            _ => None,
+        }
+    }
+
+    /// Returns a new column holding the values at `indices`, in that order.
+    pub fn take(&self, indices: &[usize]) -> Self {
+        fn pick<T: Copy>(values: &[T], indices: &[usize]) -> Vec<T> {
+            indices.iter().map(|&i| values[i]).collect()
+        }
+
+        match self {
+            Column::F32(values) => Column::F32(pick(values, indices)),
+            Column::I32(values) => Column::I32(pick(values, indices)),
+            Column::U8(values) => Column::U8(pick(values, indices)),
+            Column::Label { categories, codes } => Column::Label {
+                categories: categories.clone(),
+                codes: pick(codes, indices),
+            },
+        }
+    }
+}
+
+impl From<Vec<f32>> for Column {
+    fn from(values: Vec<f32>) -> Self {
+        Column::F32(values)
+    }
+}
+
+impl From<Vec<i32>> for Column {
+    fn from(values: Vec<i32>) -> Self {
+        Column::I32(values)
+    }
+}
+
+impl From<Vec<u8>> for Column {
+    fn from(values: Vec<u8>) -> Self {
+        Column::U8(values)
+    }
+}
+
+impl Value<'_> {
+    /// Returns the value as an `f32`, or `None` for categorical values.
+    pub fn as_f32(&self) -> Option<f32> {
+        match *self {
+            Value::F32(v) => Some(v),
+            Value::I32(v) => Some(v as f32),
+            Value::U8(v) => Some(f32::from(v)),
+            Value::Label(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::F32(v) => write!(f, "{v}"),
+            Value::I32(v) => write!(f, "{v}"),
+            Value::U8(v) => write!(f, "{v}"),
+            Value::Label(v) => write!(f, "{v}"),
+        }
+    }
+}
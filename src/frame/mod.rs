@@ -0,0 +1,221 @@
+use std::fmt;
+use std::ops::{Index, Range};
+
+use crate::datasets::Dataset;
+
+mod column;
+
+pub use column::{Column, Value};
+
+/// A table of named, typed columns of equal length.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DataFrame {
+    names: Vec<String>,
+    columns: Vec<Column>,
+}
+
+/// A single row of a [DataFrame], as seen by [DataFrame::filter].
+#[derive(Clone, Copy, Debug)]
+pub struct Row<'a> {
+    frame: &'a DataFrame,
+    index: usize,
+}
+
+impl DataFrame {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a frame with one `f32` column per feature of `records`, named after
+    /// [Dataset::FEATURE_NAMES].
+    pub fn from_features<T: Dataset>(records: &[T]) -> Self {
+        let mut columns = vec![Vec::with_capacity(records.len()); T::NUM_FEATURES];
+        let mut row = vec![0.0; T::NUM_FEATURES];
+        for record in records {
+            record.write_features(&mut row);
+            for (column, &value) in columns.iter_mut().zip(&row) {
+                column.push(value);
+            }
+        }
+
+        Self {
+            names: T::FEATURE_NAMES.iter().map(|n| n.to_string()).collect(),
+            columns: columns.into_iter().map(Column::F32).collect(),
+        }
+    }
+
+    /// Appends a column, or replaces the column that already has this name.
+    ///
+    /// Panics if the column does not have as many rows as the frame.
+    pub fn insert(&mut self, name: &str, column: impl Into<Column>) {
+        let column = column.into();
+        match self.position(name) {
+            Some(i) => {
+                assert_eq!(column.len(), self.num_rows(), "Invalid column length");
+                self.columns[i] = column;
+            }
+            None => {
+                if !self.columns.is_empty() {
+                    assert_eq!(column.len(), self.num_rows(), "Invalid column length");
+                }
+                self.names.push(name.to_string());
+                self.columns.push(column);
+            }
+        }
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.columns.first().map_or(0, Column::len)
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns `(rows, columns)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.num_rows(), self.num_columns())
+    }
+
+    pub fn column_names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the column named `name`, or `None` if there is no such column.
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.position(name).map(|i| &self.columns[i])
+    }
+
+    /// Returns a new frame with only the columns named in `names`, in that order.
+    ///
+    /// Panics if one of the names does not exist.
+    pub fn select(&self, names: &[&str]) -> Self {
+        let mut df = Self::new();
+        for &name in names {
+            df.insert(name, self[name].clone());
+        }
+        df
+    }
+
+    /// Returns a new frame with the rows at `indices`, in that order.
+    pub fn take(&self, indices: &[usize]) -> Self {
+        Self {
+            names: self.names.clone(),
+            columns: self.columns.iter().map(|c| c.take(indices)).collect(),
+        }
+    }
+
+    /// Returns a new frame with the rows in `range`.
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        assert!(range.end <= self.num_rows(), "Invalid row range");
+        self.take(&range.collect::<Vec<_>>())
+    }
+
+    /// Returns a new frame with the rows for which `predicate` returns `true`.
+    pub fn filter<P: FnMut(&Row) -> bool>(&self, mut predicate: P) -> Self {
+        let indices: Vec<usize> = (0..self.num_rows())
+            .filter(|&index| predicate(&Row { frame: self, index }))
+            .collect();
+        self.take(&indices)
+    }
+
+    /// Returns the first `n` rows.
+    pub fn head(&self, n: usize) -> Self {
+        self.slice(0..n.min(self.num_rows()))
+    }
+
+    /// Returns the last `n` rows.
+    pub fn tail(&self, n: usize) -> Self {
+        let num_rows = self.num_rows();
+        self.slice(num_rows - n.min(num_rows)..num_rows)
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+}
+
+impl Index<&str> for DataFrame {
+    type Output = Column;
+
+    fn index(&self, name: &str) -> &Self::Output {
+        self.column(name).expect("Invalid column name")
+    }
+}
+
+impl<'a> Row<'a> {
+    /// Position of this row in the frame.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the value of the column named `name`, or `None` if there is no such column.
+    pub fn get(&self, name: &str) -> Option<Value<'a>> {
+        self.frame.column(name).and_then(|c| c.get(self.index))
+    }
+}
+
+impl fmt::Display for DataFrame {
+    /// Prints the frame as an aligned table, with the row indices in the first column.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let num_rows = self.num_rows();
+
+        let mut table = vec![(0..num_rows).map(|i| i.to_string()).collect::<Vec<_>>()];
+        let mut headers = vec![String::new()];
+        for (name, column) in self.names.iter().zip(&self.columns) {
+            headers.push(name.clone());
+            table.push(
+                (0..num_rows)
+                    .map(|i| column.get(i).unwrap().to_string())
+                    .collect(),
+            );
+        }
+
+        let widths: Vec<usize> = headers
+            .iter()
+            .zip(&table)
+            .map(|(header, cells)| {
+                cells
+                    .iter()
+                    .map(String::len)
+                    .chain([header.len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut write_line = |cells: &mut dyn Iterator<Item = &String>| {
+            let line: Vec<String> = cells
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:>width$}"))
+                .collect();
+            writeln!(f, "{}", line.join("  ").trim_end())
+        };
+
+        write_line(&mut headers.iter())?;
+        for i in 0..num_rows {
+            write_line(&mut table.iter().map(|cells| &cells[i]))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn select_filter_and_print() {
+    let mut df = DataFrame::new();
+    df.insert("x", vec![1.5f32, -2.0, 3.25]);
+    df.insert("n", vec![10i32, 20, 30]);
+    df.insert("class", Column::label(&["a", "b"], vec![0, 1, 0]));
+
+    let filtered = df.filter(|row| row.get("class") == Some(Value::Label("a")));
+    assert_eq!(filtered.shape(), (2, 3));
+    assert_eq!(filtered["n"].as_i32(), Some(&[10, 30][..]));
+
+    let selected = df.select(&["class", "x"]).tail(2);
+    assert_eq!(selected.column_names(), ["class", "x"]);
+    assert_eq!(
+        selected.to_string(),
+        "   class     x\n0      b    -2\n1      a  3.25\n"
+    );
+}
@@ -1,2 +1,15 @@
+pub mod cluster;
 pub mod datasets;
+pub mod ensemble;
+pub mod frame;
+pub mod linear_model;
+pub mod matrix;
+pub mod metrics;
+pub mod model_selection;
+pub mod naive_bayes;
+pub mod neighbors;
+pub mod neural_network;
+pub mod rules;
+pub mod svm;
+pub mod tree;
 pub mod utils;
@@ -0,0 +1,335 @@
+use super::{Centered, LinearFit};
+use crate::matrix::Matrix;
+
+/// Least squares with a mix of L1 and L2 penalties, fitted by coordinate descent:
+/// minimizes `|y - x . coef - intercept|^2 / (2 n) + alpha l1_ratio |coef|_1
+/// + alpha (1 - l1_ratio) |coef|^2 / 2` over `n` samples, like scikit-learn.
+///
+/// The L1 part sets the coefficients of uninformative features to exactly zero, while the L2 part
+/// spreads the weight among correlated features instead of picking one of them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElasticNet {
+    alpha: f64,
+    l1_ratio: f64,
+    fit_intercept: bool,
+    max_iter: usize,
+    tol: f64,
+    fitted: Option<LinearFit>,
+}
+
+impl ElasticNet {
+    /// Elastic net with penalty strength `alpha` (non-negative), of which a fraction `l1_ratio`
+    /// (`0.0..=1.0`) is the L1 penalty.
+    pub fn new(alpha: f64, l1_ratio: f64) -> Self {
+        assert!(alpha >= 0.0, "Invalid alpha");
+        assert!((0.0..=1.0).contains(&l1_ratio), "Invalid L1 ratio");
+        Self {
+            alpha,
+            l1_ratio,
+            fit_intercept: true,
+            max_iter: 1000,
+            tol: 1e-4,
+            fitted: None,
+        }
+    }
+
+    /// Whether to fit an intercept (the default), or to assume the data is already centered.
+    pub fn with_intercept(mut self, fit_intercept: bool) -> Self {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+
+    /// Maximum number of passes over the features (1000 by default).
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Tolerance on the duality gap, relative to `|y|^2` (`1e-4` by default).
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    /// Fits the model to the rows of `x` and their targets `y`.
+    pub fn fit<Y: Copy + Into<f64>>(&mut self, x: &Matrix, y: &[Y]) {
+        let data = Centered::new(x, y, self.fit_intercept);
+        let coef = self.descend(&data, vec![0.0; x.num_cols()]);
+        self.fitted = Some(data.into_fit(coef));
+    }
+
+    /// Runs coordinate descent on `data`, starting from `coef`.
+    fn descend(&self, data: &Centered, mut coef: Vec<f64>) -> Vec<f64> {
+        let n = data.y.len() as f64;
+        let l1 = self.alpha * self.l1_ratio * n;
+        let l2 = self.alpha * (1.0 - self.l1_ratio) * n;
+
+        let columns = data.x.transpose();
+        let norms: Vec<f64> = columns
+            .rows()
+            .map(|column| column.iter().map(|v| v * v).sum())
+            .collect();
+        let mut residuals: Vec<f64> = data
+            .y
+            .iter()
+            .zip(data.x.matvec(&coef))
+            .map(|(y, p)| y - p)
+            .collect();
+        let tol = self.tol * dot(&data.y, &data.y);
+
+        for _ in 0..self.max_iter {
+            let (mut max_change, mut max_coef) = (0.0f64, 0.0f64);
+            for (j, column) in columns.rows().enumerate() {
+                if norms[j] == 0.0 {
+                    continue;
+                }
+                let old = coef[j];
+                let rho = dot(column, &residuals) + norms[j] * old;
+                let new = soft_threshold(rho, l1) / (norms[j] + l2);
+                if new != old {
+                    residuals
+                        .iter_mut()
+                        .zip(column)
+                        .for_each(|(r, x)| *r -= x * (new - old));
+                    coef[j] = new;
+                }
+                max_change = max_change.max((new - old).abs());
+                max_coef = max_coef.max(new.abs());
+            }
+
+            // The duality gap is costly, so only check it once the coefficients settle.
+            if max_coef == 0.0 || max_change / max_coef < self.tol {
+                let gap = duality_gap(&columns, &data.y, &residuals, &coef, l1, l2);
+                if gap < tol {
+                    break;
+                }
+            }
+        }
+        coef
+    }
+
+    /// Predicts the target of each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<f32> {
+        self.fitted.as_ref().expect("Model not fitted").predict(x)
+    }
+
+    /// Coefficient of each feature, once fitted.
+    pub fn coef(&self) -> Option<&[f64]> {
+        self.fitted.as_ref().map(|fit| fit.coef.as_slice())
+    }
+
+    /// Intercept, once fitted (0 when fitted without one).
+    pub fn intercept(&self) -> Option<f64> {
+        self.fitted.as_ref().map(|fit| fit.intercept)
+    }
+}
+
+impl Default for ElasticNet {
+    /// Elastic net with `alpha = 1` and `l1_ratio = 0.5`.
+    fn default() -> Self {
+        Self::new(1.0, 0.5)
+    }
+}
+
+/// Least squares with an L1 penalty, fitted by coordinate descent: the [ElasticNet] with
+/// `l1_ratio = 1`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lasso(ElasticNet);
+
+impl Lasso {
+    /// Lasso with penalty strength `alpha` (non-negative).
+    pub fn new(alpha: f64) -> Self {
+        Self(ElasticNet::new(alpha, 1.0))
+    }
+
+    /// Whether to fit an intercept (the default), or to assume the data is already centered.
+    pub fn with_intercept(self, fit_intercept: bool) -> Self {
+        Self(self.0.with_intercept(fit_intercept))
+    }
+
+    /// Maximum number of passes over the features (1000 by default).
+    pub fn with_max_iter(self, max_iter: usize) -> Self {
+        Self(self.0.with_max_iter(max_iter))
+    }
+
+    /// Tolerance on the duality gap, relative to `|y|^2` (`1e-4` by default).
+    pub fn with_tol(self, tol: f64) -> Self {
+        Self(self.0.with_tol(tol))
+    }
+
+    /// Fits the model to the rows of `x` and their targets `y`.
+    pub fn fit<Y: Copy + Into<f64>>(&mut self, x: &Matrix, y: &[Y]) {
+        self.0.fit(x, y);
+    }
+
+    /// Predicts the target of each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<f32> {
+        self.0.predict(x)
+    }
+
+    /// Coefficient of each feature, once fitted.
+    pub fn coef(&self) -> Option<&[f64]> {
+        self.0.coef()
+    }
+
+    /// Intercept, once fitted (0 when fitted without one).
+    pub fn intercept(&self) -> Option<f64> {
+        self.0.intercept()
+    }
+}
+
+impl Default for Lasso {
+    /// Lasso with `alpha = 1`.
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+/// Grid of `num_alphas` values of alpha for [lasso_path], evenly spaced on a log scale and
+/// decreasing from the smallest alpha for which every coefficient is zero, down to `eps` times
+/// that value.
+pub fn alpha_grid<Y: Copy + Into<f64>>(
+    x: &Matrix,
+    y: &[Y],
+    num_alphas: usize,
+    eps: f64,
+) -> Vec<f64> {
+    assert!(num_alphas >= 2, "At least two alphas are needed");
+    let data = Centered::new(x, y, true);
+    let n = data.y.len() as f64;
+    let alpha_max = data
+        .x
+        .transpose()
+        .matvec(&data.y)
+        .iter()
+        .fold(0.0, |max: f64, v| max.max(v.abs()))
+        / n;
+
+    let step = eps.ln() / (num_alphas - 1) as f64;
+    (0..num_alphas)
+        .map(|k| alpha_max * (step * k as f64).exp())
+        .collect()
+}
+
+/// Coefficients of a [Lasso] (with intercept) fitted with each of `alphas`, one row per alpha.
+///
+/// Each fit starts from the coefficients of the previous alpha, so decreasing alphas (like the
+/// ones of [alpha_grid]) are much faster to fit than independent models.
+pub fn lasso_path<Y: Copy + Into<f64>>(x: &Matrix, y: &[Y], alphas: &[f64]) -> Matrix<f64> {
+    let data = Centered::new(x, y, true);
+    let mut coef = vec![0.0; x.num_cols()];
+    let mut path = Vec::with_capacity(alphas.len() * coef.len());
+    for &alpha in alphas {
+        coef = ElasticNet::new(alpha, 1.0).descend(&data, coef);
+        path.extend_from_slice(&coef);
+    }
+    Matrix::new(alphas.len(), x.num_cols(), path)
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+fn soft_threshold(value: f64, threshold: f64) -> f64 {
+    value.signum() * (value.abs() - threshold).max(0.0)
+}
+
+/// Gap between the (`n`-scaled) elastic net objective at `coef` and its dual, which bounds how
+/// far `coef` is from the optimum.
+fn duality_gap(
+    columns: &Matrix<f64>,
+    y: &[f64],
+    residuals: &[f64],
+    coef: &[f64],
+    l1: f64,
+    l2: f64,
+) -> f64 {
+    let correlations: Vec<f64> = columns
+        .rows()
+        .zip(coef)
+        .map(|(column, c)| dot(column, residuals) - l2 * c)
+        .collect();
+    let dual_norm = correlations
+        .iter()
+        .fold(0.0, |max: f64, v| max.max(v.abs()));
+    let residual_norm2 = dot(residuals, residuals);
+    let coef_norm2 = dot(coef, coef);
+    let l1_norm: f64 = coef.iter().map(|c| c.abs()).sum();
+
+    // Scales the residuals into a feasible dual point.
+    let (scale, mut gap) = if dual_norm > l1 {
+        let scale = l1 / dual_norm;
+        (scale, 0.5 * residual_norm2 * (1.0 + scale * scale))
+    } else {
+        (1.0, residual_norm2)
+    };
+    gap += l1 * l1_norm - scale * dot(residuals, y) + 0.5 * l2 * (1.0 + scale * scale) * coef_norm2;
+    gap
+}
+
+#[test]
+fn diabetes_coefficients_match_sklearn() {
+    use crate::datasets::diabetes::Diabetes;
+    use crate::datasets::Dataset;
+
+    let (x, y) = Diabetes::to_xy(&Diabetes::get_as_vec(true));
+    let close = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 0.5);
+
+    // `sklearn.linear_model.Lasso(alpha=0.1)`, which drops three features.
+    let mut lasso = Lasso::new(0.1);
+    lasso.fit(&x, &y);
+    let expected = [
+        0.0, -155.343, 517.216, 275.087, -52.552, 0.0, -210.140, 0.0, 483.917, 33.662,
+    ];
+    let coef = lasso.coef().unwrap();
+    assert!(close(coef, &expected));
+    assert_eq!((coef[0], coef[5], coef[7]), (0.0, 0.0, 0.0));
+    assert!((lasso.intercept().unwrap() - 152.133).abs() < 1e-3);
+
+    // `sklearn.linear_model.ElasticNet(alpha=0.001, l1_ratio=0.5)`.
+    let mut enet = ElasticNet::new(0.001, 0.5);
+    enet.fit(&x, &y);
+    let expected = [
+        8.707, -178.075, 450.885, 281.069, -44.058, -77.939, -188.955, 119.795, 393.705, 98.945,
+    ];
+    assert!(close(enet.coef().unwrap(), &expected));
+}
+
+#[test]
+fn lasso_path_adds_features_like_lars() {
+    use crate::datasets::diabetes::Diabetes;
+    use crate::datasets::Dataset;
+
+    let (x, y) = Diabetes::to_xy(&Diabetes::get_as_vec(true));
+    let alphas = alpha_grid(&x, &y, 100, 1e-3);
+    assert!(alphas.windows(2).all(|pair| pair[0] > pair[1]));
+    let path = lasso_path(&x, &y, &alphas);
+    assert_eq!(path.shape(), (100, 10));
+    assert!(path.row(0).iter().all(|&c| c == 0.0));
+
+    // Features enter the model in the order bmi, ltg, bp, as in the LARS paper (bmi and ltg
+    // within the same step of the grid).
+    let entry = |j: usize| path.col(j).position(|&c| c != 0.0).unwrap();
+    assert!(entry(2) <= entry(8) && entry(8) < entry(3));
+    assert!(
+        entry(3)
+            < (0..10)
+                .filter(|&j| ![2, 8, 3].contains(&j))
+                .map(entry)
+                .min()
+                .unwrap()
+    );
+
+    // At the end of the path, the penalty is small enough to approach least squares.
+    let mut lasso = Lasso::new(alphas[99]);
+    lasso.fit(&x, &y);
+    let last: Vec<f64> = path.row(99).to_vec();
+    assert!(last
+        .iter()
+        .zip(lasso.coef().unwrap())
+        .all(|(a, b)| (a - b).abs() < 1.0));
+}
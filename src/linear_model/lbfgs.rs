@@ -0,0 +1,140 @@
+use std::collections::VecDeque;
+
+/// Number of past steps whose curvature is remembered.
+const MEMORY: usize = 10;
+
+/// Minimizes a smooth function with L-BFGS, starting from `x`.
+///
+/// `objective` returns the value and the gradient of the function at a point. Stops once every
+/// component of the gradient is at most `tol` in absolute value, or after `max_iter` iterations.
+pub(super) fn minimize(
+    objective: impl Fn(&[f64]) -> (f64, Vec<f64>),
+    mut x: Vec<f64>,
+    max_iter: usize,
+    tol: f64,
+) -> Vec<f64> {
+    let (mut value, mut gradient) = objective(&x);
+    // Pairs of position and gradient differences `(s, y)` of the last steps.
+    let mut history: VecDeque<(Vec<f64>, Vec<f64>)> = VecDeque::with_capacity(MEMORY);
+
+    for _ in 0..max_iter {
+        if max_abs(&gradient) <= tol {
+            break;
+        }
+
+        let mut direction = two_loop(&history, &gradient);
+        if history.is_empty() {
+            // Without curvature information, the first step is scaled to unit length.
+            let norm = dot(&gradient, &gradient).sqrt();
+            direction.iter_mut().for_each(|d| *d /= norm);
+        }
+        let mut slope = dot(&direction, &gradient);
+        if slope <= 0.0 {
+            // Not a descent direction: restart from steepest descent.
+            history.clear();
+            direction = gradient.clone();
+            slope = dot(&gradient, &gradient);
+        }
+
+        // Backtracking line search for sufficient decrease (Armijo condition).
+        let mut step = 1.0;
+        let accepted = loop {
+            let next: Vec<f64> = x
+                .iter()
+                .zip(&direction)
+                .map(|(x, d)| x - step * d)
+                .collect();
+            let (next_value, next_gradient) = objective(&next);
+            if next_value <= value - 1e-4 * step * slope {
+                break Some((next, next_value, next_gradient));
+            }
+            step /= 2.0;
+            if step < 1e-12 {
+                break None;
+            }
+        };
+        let Some((next, next_value, next_gradient)) = accepted else {
+            // No step decreases the objective: retry from steepest descent, or stop at the
+            // current point if that was already the direction.
+            if history.is_empty() {
+                break;
+            }
+            history.clear();
+            continue;
+        };
+
+        let s: Vec<f64> = next.iter().zip(&x).map(|(a, b)| a - b).collect();
+        let y: Vec<f64> = next_gradient
+            .iter()
+            .zip(&gradient)
+            .map(|(a, b)| a - b)
+            .collect();
+        let converged = value - next_value <= f64::EPSILON * value.abs().max(1.0);
+        // Only pairs with positive curvature keep the inverse Hessian estimate positive definite.
+        if dot(&s, &y) > 1e-10 {
+            if history.len() == MEMORY {
+                history.pop_front();
+            }
+            history.push_back((s, y));
+        }
+        (x, value, gradient) = (next, next_value, next_gradient);
+        if converged {
+            break;
+        }
+    }
+    x
+}
+
+/// Product of the inverse Hessian estimate from `history` with `gradient`.
+fn two_loop(history: &VecDeque<(Vec<f64>, Vec<f64>)>, gradient: &[f64]) -> Vec<f64> {
+    let mut q = gradient.to_vec();
+    let mut alphas = Vec::with_capacity(history.len());
+    for (s, y) in history.iter().rev() {
+        let alpha = dot(s, &q) / dot(s, y);
+        q.iter_mut().zip(y).for_each(|(q, y)| *q -= alpha * y);
+        alphas.push(alpha);
+    }
+
+    if let Some((s, y)) = history.back() {
+        let gamma = dot(s, y) / dot(y, y);
+        q.iter_mut().for_each(|q| *q *= gamma);
+    }
+
+    for ((s, y), alpha) in history.iter().zip(alphas.into_iter().rev()) {
+        let beta = dot(y, &q) / dot(s, y);
+        q.iter_mut()
+            .zip(s)
+            .for_each(|(q, s)| *q += (alpha - beta) * s);
+    }
+    q
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+fn max_abs(values: &[f64]) -> f64 {
+    values.iter().fold(0.0, |max: f64, v| max.max(v.abs()))
+}
+
+#[test]
+fn minimizes_rosenbrock() {
+    let rosenbrock = |p: &[f64]| {
+        let (x, y) = (p[0], p[1]);
+        let value = (1.0 - x).powi(2) + 100.0 * (y - x * x).powi(2);
+        let gradient = vec![
+            -2.0 * (1.0 - x) - 400.0 * x * (y - x * x),
+            200.0 * (y - x * x),
+        ];
+        (value, gradient)
+    };
+    let minimum = minimize(rosenbrock, vec![-1.2, 1.0], 1000, 1e-8);
+    assert!((minimum[0] - 1.0).abs() < 1e-5 && (minimum[1] - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn stops_when_no_step_decreases() {
+    // The gradient is wrong at the minimum, so every step along it goes uphill.
+    let objective = |p: &[f64]| (p[0] * p[0], vec![1.0]);
+    assert_eq!(minimize(objective, vec![0.0], 100, 1e-8), [0.0]);
+}
@@ -0,0 +1,170 @@
+use super::{Centered, LinearFit};
+use crate::matrix::Matrix;
+
+/// Ordinary least squares: minimizes the sum of squared residuals `|y - x . coef - intercept|^2`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearRegression {
+    fit_intercept: bool,
+    fitted: Option<LinearFit>,
+}
+
+impl LinearRegression {
+    pub fn new() -> Self {
+        Self {
+            fit_intercept: true,
+            fitted: None,
+        }
+    }
+
+    /// Whether to fit an intercept (the default), or to assume the data is already centered.
+    pub fn with_intercept(mut self, fit_intercept: bool) -> Self {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+
+    /// Fits the model to the rows of `x` and their targets `y`.
+    ///
+    /// Panics if the features are linearly dependent, e.g. if one of them is constant.
+    pub fn fit<Y: Copy + Into<f64>>(&mut self, x: &Matrix, y: &[Y]) {
+        let data = Centered::new(x, y, self.fit_intercept);
+        let coef = data
+            .x
+            .solve_least_squares(&data.y)
+            .expect("Features are linearly dependent");
+        self.fitted = Some(data.into_fit(coef));
+    }
+
+    /// Predicts the target of each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<f32> {
+        self.fitted.as_ref().expect("Model not fitted").predict(x)
+    }
+
+    /// Coefficient of each feature, once fitted.
+    pub fn coef(&self) -> Option<&[f64]> {
+        self.fitted.as_ref().map(|fit| fit.coef.as_slice())
+    }
+
+    /// Intercept, once fitted (0 when fitted without one).
+    pub fn intercept(&self) -> Option<f64> {
+        self.fitted.as_ref().map(|fit| fit.intercept)
+    }
+}
+
+impl Default for LinearRegression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Least squares with an L2 penalty: minimizes `|y - x . coef - intercept|^2 + alpha |coef|^2`.
+///
+/// The penalty shrinks the coefficients towards zero, which stabilizes them when features are
+/// correlated. The intercept is not penalized.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ridge {
+    alpha: f64,
+    fit_intercept: bool,
+    fitted: Option<LinearFit>,
+}
+
+impl Ridge {
+    /// Ridge regression with penalty strength `alpha` (non-negative).
+    pub fn new(alpha: f64) -> Self {
+        assert!(alpha >= 0.0, "Invalid alpha");
+        Self {
+            alpha,
+            fit_intercept: true,
+            fitted: None,
+        }
+    }
+
+    /// Whether to fit an intercept (the default), or to assume the data is already centered.
+    pub fn with_intercept(mut self, fit_intercept: bool) -> Self {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+
+    /// Fits the model to the rows of `x` and their targets `y`, by solving the closed form
+    /// `(x^T x + alpha I) coef = x^T y`.
+    ///
+    /// Panics if `alpha` is 0 and the features are linearly dependent.
+    pub fn fit<Y: Copy + Into<f64>>(&mut self, x: &Matrix, y: &[Y]) {
+        let data = Centered::new(x, y, self.fit_intercept);
+        let xt = data.x.transpose();
+
+        let mut gram = xt.matmul(&data.x);
+        for j in 0..gram.num_rows() {
+            gram[(j, j)] += self.alpha;
+        }
+        let coef = gram
+            .solve_cholesky(&xt.matvec(&data.y))
+            .expect("Features are linearly dependent");
+        self.fitted = Some(data.into_fit(coef));
+    }
+
+    /// Predicts the target of each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<f32> {
+        self.fitted.as_ref().expect("Model not fitted").predict(x)
+    }
+
+    /// Coefficient of each feature, once fitted.
+    pub fn coef(&self) -> Option<&[f64]> {
+        self.fitted.as_ref().map(|fit| fit.coef.as_slice())
+    }
+
+    /// Intercept, once fitted (0 when fitted without one).
+    pub fn intercept(&self) -> Option<f64> {
+        self.fitted.as_ref().map(|fit| fit.intercept)
+    }
+}
+
+impl Default for Ridge {
+    /// Ridge regression with `alpha = 1`.
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+#[test]
+fn diabetes_coefficients_match_sklearn() {
+    use crate::datasets::diabetes::Diabetes;
+    use crate::datasets::Dataset;
+    use crate::metrics::regression::r2_score;
+
+    // Features scaled like `sklearn.datasets.load_diabetes`, where the least-squares
+    // coefficients are the well-known ones from the LARS paper.
+    let (x, y) = Diabetes::to_xy(&Diabetes::get_as_vec(true));
+    let close = |a: &[f64], b: &[f64], tol: f64| a.iter().zip(b).all(|(a, b)| (a - b).abs() < tol);
+
+    let mut ols = LinearRegression::new();
+    ols.fit(&x, &y);
+    let expected = [
+        -10.0099, -239.8156, 519.8459, 324.3846, -792.1756, 476.7390, 101.0433, 177.0632, 751.2737,
+        67.6267,
+    ];
+    assert!(close(ols.coef().unwrap(), &expected, 0.05));
+    assert!((ols.intercept().unwrap() - 152.1335).abs() < 1e-3);
+    assert!((r2_score(&y, &ols.predict(&x)) - 0.5177).abs() < 1e-4);
+
+    // `sklearn.linear_model.Ridge(alpha=0.1)`.
+    let mut ridge = Ridge::new(0.1);
+    ridge.fit(&x, &y);
+    let expected = [
+        1.3087, -207.1924, 489.6952, 301.7641, -83.4660, -70.8268, -188.6789, 115.7121, 443.8129,
+        86.7493,
+    ];
+    assert!(close(ridge.coef().unwrap(), &expected, 0.05));
+    assert!((ridge.intercept().unwrap() - 152.1335).abs() < 1e-3);
+
+    let mut unpenalized = Ridge::new(0.0);
+    unpenalized.fit(&x, &y);
+    assert!(close(
+        unpenalized.coef().unwrap(),
+        ols.coef().unwrap(),
+        0.05
+    ));
+}
@@ -0,0 +1,293 @@
+use std::marker::PhantomData;
+
+use super::lbfgs;
+use crate::datasets::ClassLabel;
+use crate::matrix::Matrix;
+
+/// Logistic regression classifier with an L2 penalty, fitted with L-BFGS.
+///
+/// Minimizes the mean cross-entropy plus `|coef|^2 / (2 C n)` over `n` samples (the intercept is
+/// not penalized), which has the same solution as scikit-learn's `C`-weighted objective. Two
+/// classes are modeled with a single logistic function, more classes with a multinomial
+/// (softmax) model.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogisticRegression<L> {
+    c: f64,
+    fit_intercept: bool,
+    max_iter: usize,
+    tol: f64,
+    fitted: Option<LogisticFit>,
+    labels: PhantomData<L>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct LogisticFit {
+    num_classes: usize,
+
+    /// One row of coefficients for two classes (the log-odds of the second one), or one row per
+    /// class.
+    coef: Matrix<f64>,
+    intercept: Vec<f64>,
+}
+
+impl LogisticFit {
+    /// Returns the probability of each class for each row of `x`.
+    fn predict_proba(&self, x: &Matrix) -> Matrix<f64> {
+        assert_eq!(
+            x.num_cols(),
+            self.coef.num_cols(),
+            "Invalid number of columns"
+        );
+        let mut probabilities = Matrix::zeros(x.num_rows(), self.num_classes);
+        let mut logits = vec![0.0; self.coef.num_rows()];
+        for (i, row) in x.rows().enumerate() {
+            for (k, logit) in logits.iter_mut().enumerate() {
+                *logit = self.intercept[k]
+                    + row
+                        .iter()
+                        .zip(self.coef.row(k))
+                        .map(|(&x, c)| x as f64 * c)
+                        .sum::<f64>();
+            }
+            probabilities
+                .row_mut(i)
+                .copy_from_slice(&class_probabilities(&logits));
+        }
+        probabilities
+    }
+}
+
+impl<L: ClassLabel> LogisticRegression<L> {
+    pub fn new() -> Self {
+        Self {
+            c: 1.0,
+            fit_intercept: true,
+            max_iter: 100,
+            tol: 1e-4,
+            fitted: None,
+            labels: PhantomData,
+        }
+    }
+
+    /// Inverse of the penalty strength (1 by default): smaller values mean a stronger penalty.
+    pub fn with_c(mut self, c: f64) -> Self {
+        assert!(c > 0.0, "Invalid C");
+        self.c = c;
+        self
+    }
+
+    /// Whether to fit an intercept (the default).
+    pub fn with_intercept(mut self, fit_intercept: bool) -> Self {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+
+    /// Maximum number of L-BFGS iterations (100 by default). Unscaled features may need more.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Tolerance on the largest component of the gradient (`1e-4` by default).
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    /// Fits the model to the rows of `x` and their labels `y`.
+    ///
+    /// The classes are the labels up to the largest [ClassLabel::index] in `y`.
+    pub fn fit(&mut self, x: &Matrix, y: &[L]) {
+        assert_eq!(x.num_rows(), y.len(), "Inconsistent number of samples");
+        let num_classes = y.iter().map(|label| label.index() + 1).max().unwrap_or(0);
+        assert!(num_classes >= 2, "At least two classes are needed");
+
+        let x = x.map(f64::from);
+        let targets: Vec<usize> = y.iter().map(ClassLabel::index).collect();
+        let num_rows = if num_classes == 2 { 1 } else { num_classes };
+        let width = x.num_cols() + 1;
+        let (n, penalty) = (y.len() as f64, 1.0 / (self.c * y.len() as f64));
+
+        // Parameters are laid out as one row per class: the coefficients, then the intercept.
+        let objective = |params: &[f64]| {
+            let mut loss = 0.0;
+            let mut gradient = vec![0.0; params.len()];
+            let mut logits = vec![0.0; num_rows];
+
+            for (row, &target) in x.rows().zip(&targets) {
+                for (k, logit) in logits.iter_mut().enumerate() {
+                    let w = &params[k * width..(k + 1) * width];
+                    *logit = w[width - 1] + row.iter().zip(w).map(|(x, w)| x * w).sum::<f64>();
+                }
+                let probabilities = class_probabilities(&logits);
+                loss -= probabilities[target].max(f64::MIN_POSITIVE).ln();
+
+                // Derivative of the loss with respect to each logit.
+                let errors: Vec<f64> = if num_rows == 1 {
+                    vec![probabilities[1] - (target == 1) as u8 as f64]
+                } else {
+                    (0..num_rows)
+                        .map(|k| probabilities[k] - (target == k) as u8 as f64)
+                        .collect()
+                };
+                for (k, error) in errors.into_iter().enumerate() {
+                    let g = &mut gradient[k * width..(k + 1) * width];
+                    g.iter_mut().zip(row).for_each(|(g, x)| *g += error * x);
+                    g[width - 1] += error;
+                }
+            }
+
+            loss /= n;
+            gradient.iter_mut().for_each(|g| *g /= n);
+            for (j, (g, w)) in gradient.iter_mut().zip(params).enumerate() {
+                if j % width == width - 1 {
+                    if !self.fit_intercept {
+                        *g = 0.0;
+                    }
+                } else {
+                    loss += 0.5 * penalty * w * w;
+                    *g += penalty * w;
+                }
+            }
+            (loss, gradient)
+        };
+
+        let params = lbfgs::minimize(
+            objective,
+            vec![0.0; num_rows * width],
+            self.max_iter,
+            self.tol,
+        );
+        let params = Matrix::new(num_rows, width, params);
+        self.fitted = Some(LogisticFit {
+            num_classes,
+            coef: params.select_cols(&(0..width - 1).collect::<Vec<_>>()),
+            intercept: params.col(width - 1).copied().collect(),
+        });
+    }
+
+    /// Returns the probability of each class (columns, in [ClassLabel::index] order) for each row
+    /// of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict_proba(&self, x: &Matrix) -> Matrix<f64> {
+        self.fitted
+            .as_ref()
+            .expect("Model not fitted")
+            .predict_proba(x)
+    }
+
+    /// Predicts the most probable class of each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<L> {
+        self.predict_proba(x)
+            .rows()
+            .map(|row| L::from_index(argmax(row)))
+            .collect()
+    }
+
+    /// Coefficients, once fitted: a single row for two classes (the log-odds of the second
+    /// class), or one row per class.
+    pub fn coef(&self) -> Option<&Matrix<f64>> {
+        self.fitted.as_ref().map(|fit| &fit.coef)
+    }
+
+    /// Intercept of each row of [LogisticRegression::coef], once fitted.
+    pub fn intercept(&self) -> Option<&[f64]> {
+        self.fitted.as_ref().map(|fit| fit.intercept.as_slice())
+    }
+
+    /// Pairs each feature name, typically from the `FEATURE_NAMES` of the dataset, with its
+    /// coefficient in each row of [LogisticRegression::coef], once fitted.
+    pub fn named_coef<'a>(&self, feature_names: &[&'a str]) -> Option<Vec<(&'a str, Vec<f64>)>> {
+        let coef = self.coef()?;
+        assert_eq!(
+            feature_names.len(),
+            coef.num_cols(),
+            "Invalid number of names"
+        );
+        Some(
+            feature_names
+                .iter()
+                .enumerate()
+                .map(|(j, &name)| (name, coef.col(j).copied().collect()))
+                .collect(),
+        )
+    }
+}
+
+impl<L: ClassLabel> Default for LogisticRegression<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Turns one logit (the log-odds of the second of two classes) or one logit per class into
+/// class probabilities.
+fn class_probabilities(logits: &[f64]) -> Vec<f64> {
+    if let [logit] = logits {
+        let p = 1.0 / (1.0 + (-logit).exp());
+        return vec![1.0 - p, p];
+    }
+    let max = logits.iter().fold(f64::NEG_INFINITY, |max, &l| max.max(l));
+    let exps: Vec<f64> = logits.iter().map(|l| (l - max).exp()).collect();
+    let sum: f64 = exps.iter().sum();
+    exps.iter().map(|e| e / sum).collect()
+}
+
+fn argmax(values: &[f64]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .fold(0, |best, (k, &v)| if v > values[best] { k } else { best })
+}
+
+#[test]
+fn binary_matches_sklearn() {
+    use crate::datasets::breast_cancer::{self, BreastCancer, Label};
+    use crate::datasets::Dataset;
+    use crate::metrics::classification::accuracy;
+    use crate::utils::scale::{Scaler, StandardScaler};
+
+    // `sklearn.linear_model.LogisticRegression()` on a single overlapping feature.
+    let x = Matrix::new(6, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    let y: [u8; 6] = [0, 0, 1, 0, 1, 1];
+    let mut model = LogisticRegression::new().with_tol(1e-8);
+    model.fit(&x, &y);
+    assert!((model.coef().unwrap()[(0, 0)] - 0.767054).abs() < 1e-4);
+    assert!((model.intercept().unwrap()[0] + 1.917634).abs() < 1e-4);
+    assert_eq!(model.predict(&x), [0, 0, 0, 1, 1, 1]);
+
+    // Standardized features, on which the default number of iterations is enough.
+    let (x, y) = BreastCancer::to_xy(&breast_cancer::DATA);
+    let x = Matrix::from_rows(&StandardScaler::new().fit_transform(&x.rows().collect::<Vec<_>>()));
+    let mut model = LogisticRegression::<Label>::new();
+    model.fit(&x, &y);
+    assert!(accuracy(&y, &model.predict(&x)) > 0.98);
+    assert_eq!(model.coef().unwrap().shape(), (1, 30));
+}
+
+#[test]
+fn multinomial_on_iris() {
+    use crate::datasets::iris::{self, Iris, Species};
+    use crate::datasets::Dataset;
+    use crate::metrics::classification::accuracy;
+
+    let (x, y) = Iris::to_xy(iris::DATA);
+    let mut model = LogisticRegression::<Species>::new().with_max_iter(1000);
+    model.fit(&x, &y);
+    assert!(accuracy(&y, &model.predict(&x)) > 0.96);
+
+    let probabilities = model.predict_proba(&x);
+    assert_eq!(probabilities.shape(), (150, 3));
+    assert!(probabilities
+        .rows()
+        .all(|row| (row.iter().sum::<f64>() - 1.0).abs() < 1e-9));
+
+    // Petal size separates the species much better than sepal size.
+    let named = model.named_coef(&Iris::FEATURE_NAMES).unwrap();
+    assert_eq!(named.len(), 4);
+    let spread = |coef: &[f64]| coef.iter().fold(0.0, |max: f64, c| max.max(c.abs()));
+    assert!(spread(&named[2].1) > spread(&named[1].1));
+}
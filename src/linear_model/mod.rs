@@ -0,0 +1,80 @@
+use crate::matrix::Matrix;
+
+mod coordinate_descent;
+mod lbfgs;
+mod least_squares;
+mod logistic;
+
+pub use coordinate_descent::{alpha_grid, lasso_path, ElasticNet, Lasso};
+pub use least_squares::{LinearRegression, Ridge};
+pub use logistic::LogisticRegression;
+
+/// Coefficients and intercept of a fitted linear model, which predicts `x . coef + intercept`.
+#[derive(Clone, Debug, PartialEq)]
+struct LinearFit {
+    coef: Vec<f64>,
+    intercept: f64,
+}
+
+impl LinearFit {
+    fn predict(&self, x: &Matrix) -> Vec<f32> {
+        assert_eq!(x.num_cols(), self.coef.len(), "Invalid number of columns");
+        x.rows()
+            .map(|row| {
+                let dot: f64 = row.iter().zip(&self.coef).map(|(&x, c)| x as f64 * c).sum();
+                (dot + self.intercept) as f32
+            })
+            .collect()
+    }
+}
+
+/// Training data converted to `f64`, with every column and the target centered on their mean
+/// when fitting an intercept.
+///
+/// Fitting the coefficients on centered data leaves the intercept out of the penalty of
+/// regularized models, and the intercept then follows from the means.
+struct Centered {
+    x: Matrix<f64>,
+    y: Vec<f64>,
+    x_mean: Vec<f64>,
+    y_mean: f64,
+}
+
+impl Centered {
+    fn new<Y: Copy + Into<f64>>(x: &Matrix, y: &[Y], fit_intercept: bool) -> Self {
+        assert_eq!(x.num_rows(), y.len(), "Inconsistent number of samples");
+        assert!(!y.is_empty(), "No samples");
+
+        let n = y.len() as f64;
+        let mut x = x.map(f64::from);
+        let mut y: Vec<f64> = y.iter().map(|&v| v.into()).collect();
+        if !fit_intercept {
+            return Self {
+                x_mean: vec![0.0; x.num_cols()],
+                x,
+                y,
+                y_mean: 0.0,
+            };
+        }
+
+        let x_mean: Vec<f64> = x.col_sums().iter().map(|sum| sum / n).collect();
+        let y_mean = y.iter().sum::<f64>() / n;
+        x = x.sub_row(&x_mean);
+        y.iter_mut().for_each(|v| *v -= y_mean);
+        Self {
+            x,
+            y,
+            x_mean,
+            y_mean,
+        }
+    }
+
+    /// Completes coefficients fitted on the centered data with the matching intercept.
+    fn into_fit(self, coef: Vec<f64>) -> LinearFit {
+        let offset: f64 = self.x_mean.iter().zip(&coef).map(|(m, c)| m * c).sum();
+        LinearFit {
+            intercept: self.y_mean - offset,
+            coef,
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use super::Matrix;
+
+/// Pivots smaller than this, relative to the largest one, are treated as zero.
+const RANK_TOLERANCE: f64 = 1e-12;
+
+impl Matrix<f64> {
+    /// Least-squares solution `x` of `self * x = b` (Householder QR), for a matrix with at least
+    /// as many rows as columns.
+    ///
+    /// Returns `None` if the columns of the matrix are linearly dependent.
+    pub fn solve_least_squares(&self, b: &[f64]) -> Option<Vec<f64>> {
+        let (m, n) = self.shape();
+        assert!(m >= n, "Underdetermined system");
+        assert_eq!(b.len(), m, "Invalid vector length");
+
+        let mut r = self.clone();
+        let mut qtb = b.to_vec();
+        let mut v = vec![0.0; m];
+
+        for k in 0..n {
+            let norm = (k..m).map(|i| r[(i, k)].powi(2)).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                return None;
+            }
+            let alpha = if r[(k, k)] > 0.0 { -norm } else { norm };
+
+            // Householder reflection `I - 2 v v^T / (v^T v)` that maps column k onto `alpha e_k`.
+            for i in k..m {
+                v[i] = r[(i, k)];
+            }
+            v[k] -= alpha;
+            let v_norm2: f64 = v[k..m].iter().map(|x| x * x).sum();
+
+            for j in k..n {
+                let dot: f64 = (k..m).map(|i| v[i] * r[(i, j)]).sum();
+                let factor = 2.0 * dot / v_norm2;
+                for i in k..m {
+                    r[(i, j)] -= factor * v[i];
+                }
+            }
+            let dot: f64 = (k..m).map(|i| v[i] * qtb[i]).sum();
+            let factor = 2.0 * dot / v_norm2;
+            for i in k..m {
+                qtb[i] -= factor * v[i];
+            }
+        }
+
+        let max_pivot = (0..n).fold(0.0, |max: f64, k| max.max(r[(k, k)].abs()));
+        if (0..n).any(|k| r[(k, k)].abs() <= RANK_TOLERANCE * max_pivot) {
+            return None;
+        }
+        Some(back_substitution(&r, &qtb[..n]))
+    }
+
+    /// Solution `x` of `self * x = b` for a symmetric positive definite matrix (Cholesky
+    /// decomposition).
+    ///
+    /// Returns `None` if the matrix is not positive definite.
+    pub fn solve_cholesky(&self, b: &[f64]) -> Option<Vec<f64>> {
+        let n = self.num_rows();
+        assert_eq!(self.num_cols(), n, "Matrix is not square");
+        assert_eq!(b.len(), n, "Invalid vector length");
+
+        // Lower triangular `l` such that `self = l * l^T`.
+        let mut l = Matrix::zeros(n, n);
+        for i in 0..n {
+            for j in 0..=i {
+                let sum: f64 = (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum();
+                if i == j {
+                    let pivot = self[(i, i)] - sum;
+                    if pivot <= 0.0 {
+                        return None;
+                    }
+                    l[(i, i)] = pivot.sqrt();
+                } else {
+                    l[(i, j)] = (self[(i, j)] - sum) / l[(j, j)];
+                }
+            }
+        }
+
+        // Forward substitution for `l * y = b`, then back substitution for `l^T * x = y`.
+        let mut y = vec![0.0; n];
+        for i in 0..n {
+            let sum: f64 = (0..i).map(|k| l[(i, k)] * y[k]).sum();
+            y[i] = (b[i] - sum) / l[(i, i)];
+        }
+        Some(back_substitution(&l.transpose(), &y))
+    }
+}
+
+/// Solves `r * x = b` for the upper triangular top square of `r`.
+fn back_substitution(r: &Matrix<f64>, b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let sum: f64 = (i + 1..n).map(|j| r[(i, j)] * x[j]).sum();
+        x[i] = (b[i] - sum) / r[(i, i)];
+    }
+    x
+}
+
+#[test]
+fn solvers() {
+    // Overdetermined but consistent system, with solution (1, -2).
+    let a = Matrix::new(3, 2, vec![1.0, 1.0, 2.0, 1.0, 0.0, 3.0]);
+    let x = a.solve_least_squares(&[-1.0, 0.0, -6.0]).unwrap();
+    assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] + 2.0).abs() < 1e-12);
+
+    let dependent = Matrix::new(3, 2, vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0]);
+    assert_eq!(dependent.solve_least_squares(&[1.0, 2.0, 3.0]), None);
+
+    let spd = Matrix::new(2, 2, vec![4.0, 2.0, 2.0, 3.0]);
+    let x = spd.solve_cholesky(&[2.0, -1.0]).unwrap();
+    assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] + 1.0).abs() < 1e-12);
+    assert_eq!(Matrix::new(1, 1, vec![-1.0]).solve_cholesky(&[1.0]), None);
+}
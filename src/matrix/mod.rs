@@ -0,0 +1,171 @@
+use std::ops::{Index, IndexMut};
+
+mod linalg;
+mod ops;
+
+pub use ops::Scalar;
+
+/// A dense, row-major 2D matrix.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Matrix<T = f32> {
+    num_rows: usize,
+    num_cols: usize,
+    data: Vec<T>,
+}
+
+impl<T: Copy> Matrix<T> {
+    /// Creates a matrix from its values in row-major order.
+    ///
+    /// Panics if `data` does not hold `num_rows * num_cols` values.
+    pub fn new(num_rows: usize, num_cols: usize, data: Vec<T>) -> Self {
+        assert_eq!(data.len(), num_rows * num_cols, "Invalid matrix shape");
+        Self {
+            num_rows,
+            num_cols,
+            data,
+        }
+    }
+
+    /// Creates a matrix with every value set to `value`.
+    pub fn filled(num_rows: usize, num_cols: usize, value: T) -> Self {
+        Self::new(num_rows, num_cols, vec![value; num_rows * num_cols])
+    }
+
+    /// Creates a matrix from equally long rows.
+    pub fn from_rows<R: AsRef<[T]>>(rows: &[R]) -> Self {
+        let num_cols = rows.first().map_or(0, |row| row.as_ref().len());
+        let mut data = Vec::with_capacity(rows.len() * num_cols);
+        for row in rows {
+            assert_eq!(row.as_ref().len(), num_cols, "Invalid row length");
+            data.extend_from_slice(row.as_ref());
+        }
+        Self::new(rows.len(), num_cols, data)
+    }
+
+    /// Creates a matrix with a single column.
+    pub fn from_column(values: &[T]) -> Self {
+        Self::new(values.len(), 1, values.to_vec())
+    }
+
+    /// Returns `(rows, columns)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.num_rows, self.num_cols)
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// The values in row-major order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        assert!(i < self.num_rows, "Invalid index");
+        &self.data[i * self.num_cols..(i + 1) * self.num_cols]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        assert!(i < self.num_rows, "Invalid index");
+        &mut self.data[i * self.num_cols..(i + 1) * self.num_cols]
+    }
+
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[T]> + '_ {
+        // `chunks_exact` panics on a chunk size of zero, which matrices without columns have.
+        self.data
+            .chunks_exact(self.num_cols.max(1))
+            .take(self.num_rows)
+    }
+
+    /// Returns the values of column `j`, from top to bottom.
+    pub fn col(&self, j: usize) -> impl ExactSizeIterator<Item = &T> + '_ {
+        assert!(j < self.num_cols, "Invalid index");
+        self.data
+            .get(j..)
+            .unwrap_or(&[])
+            .iter()
+            .step_by(self.num_cols)
+    }
+
+    /// Returns a new matrix with the rows at `indices`, in that order.
+    pub fn select_rows(&self, indices: &[usize]) -> Self {
+        let mut data = Vec::with_capacity(indices.len() * self.num_cols);
+        for &i in indices {
+            data.extend_from_slice(self.row(i));
+        }
+        Self::new(indices.len(), self.num_cols, data)
+    }
+
+    /// Returns a new matrix with the columns at `indices`, in that order.
+    pub fn select_cols(&self, indices: &[usize]) -> Self {
+        let mut data = Vec::with_capacity(self.num_rows * indices.len());
+        for row in self.rows() {
+            data.extend(indices.iter().map(|&j| row[j]));
+        }
+        Self::new(self.num_rows, indices.len(), data)
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut data = Vec::with_capacity(self.data.len());
+        for j in 0..self.num_cols {
+            data.extend(self.col(j).copied());
+        }
+        Self::new(self.num_cols, self.num_rows, data)
+    }
+
+    /// Applies `f` to every value.
+    pub fn map<U: Copy>(&self, f: impl FnMut(T) -> U) -> Matrix<U> {
+        Matrix::new(
+            self.num_rows,
+            self.num_cols,
+            self.data.iter().copied().map(f).collect(),
+        )
+    }
+
+    /// Combines the values of two matrices of the same shape with `f`.
+    pub fn zip_map<U: Copy, V: Copy>(
+        &self,
+        other: &Matrix<U>,
+        mut f: impl FnMut(T, U) -> V,
+    ) -> Matrix<V> {
+        assert_eq!(self.shape(), other.shape(), "Invalid matrix shape");
+        Matrix::new(
+            self.num_rows,
+            self.num_cols,
+            self.data
+                .iter()
+                .zip(&other.data)
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+        )
+    }
+}
+
+impl<T: Copy> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(i < self.num_rows && j < self.num_cols, "Invalid index");
+        &self.data[i * self.num_cols + j]
+    }
+}
+
+impl<T: Copy> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(i < self.num_rows && j < self.num_cols, "Invalid index");
+        &mut self.data[i * self.num_cols + j]
+    }
+}
@@ -0,0 +1,157 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+use super::Matrix;
+
+/// Numeric types a [Matrix] can do arithmetic with.
+pub trait Scalar:
+    Copy
+    + Default
+    + PartialEq
+    + From<u8>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+}
+
+impl<T> Scalar for T where
+    T: Copy
+        + Default
+        + PartialEq
+        + From<u8>
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+{
+}
+
+impl<T: Scalar> Matrix<T> {
+    pub fn zeros(num_rows: usize, num_cols: usize) -> Self {
+        Self::filled(num_rows, num_cols, T::default())
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut identity = Self::zeros(n, n);
+        for i in 0..n {
+            identity[(i, i)] = T::from(1);
+        }
+        identity
+    }
+
+    /// Matrix product `self * other`.
+    pub fn matmul(&self, other: &Self) -> Self {
+        assert_eq!(self.num_cols, other.num_rows, "Invalid matrix shape");
+
+        let mut product = Self::zeros(self.num_rows, other.num_cols);
+        for i in 0..self.num_rows {
+            let out = &mut product.data[i * other.num_cols..(i + 1) * other.num_cols];
+            for (k, &a) in self.row(i).iter().enumerate() {
+                for (o, &b) in out.iter_mut().zip(other.row(k)) {
+                    *o = *o + a * b;
+                }
+            }
+        }
+        product
+    }
+
+    /// Matrix-vector product `self * vector`.
+    pub fn matvec(&self, vector: &[T]) -> Vec<T> {
+        assert_eq!(self.num_cols, vector.len(), "Invalid vector length");
+        self.rows()
+            .map(|row| {
+                row.iter()
+                    .zip(vector)
+                    .fold(T::default(), |sum, (&a, &b)| sum + a * b)
+            })
+            .collect()
+    }
+
+    /// Sum of the values of each column.
+    pub fn col_sums(&self) -> Vec<T> {
+        let mut sums = vec![T::default(); self.num_cols];
+        for row in self.rows() {
+            for (sum, &value) in sums.iter_mut().zip(row) {
+                *sum = *sum + value;
+            }
+        }
+        sums
+    }
+
+    pub fn add_row(&self, row: &[T]) -> Self {
+        self.broadcast_row(row, |a, b| a + b)
+    }
+
+    pub fn sub_row(&self, row: &[T]) -> Self {
+        self.broadcast_row(row, |a, b| a - b)
+    }
+
+    pub fn mul_row(&self, row: &[T]) -> Self {
+        self.broadcast_row(row, |a, b| a * b)
+    }
+
+    pub fn div_row(&self, row: &[T]) -> Self {
+        self.broadcast_row(row, |a, b| a / b)
+    }
+
+    /// Combines every row of the matrix with `row`, value by value.
+    fn broadcast_row(&self, row: &[T], f: impl Fn(T, T) -> T) -> Self {
+        assert_eq!(row.len(), self.num_cols, "Invalid row length");
+        let mut data = Vec::with_capacity(self.data.len());
+        for r in self.rows() {
+            data.extend(r.iter().zip(row).map(|(&a, &b)| f(a, b)));
+        }
+        Self::new(self.num_rows, self.num_cols, data)
+    }
+}
+
+/// Implements an elementwise operator between matrices, and between a matrix and a scalar.
+macro_rules! elementwise_op {
+    ($trait: ident, $method: ident, $op: tt) => {
+        impl<T: Scalar> $trait for &Matrix<T> {
+            type Output = Matrix<T>;
+
+            fn $method(self, other: Self) -> Matrix<T> {
+                self.zip_map(other, |a, b| a $op b)
+            }
+        }
+
+        impl<T: Scalar> $trait<T> for &Matrix<T> {
+            type Output = Matrix<T>;
+
+            fn $method(self, scalar: T) -> Matrix<T> {
+                self.map(|a| a $op scalar)
+            }
+        }
+    };
+}
+
+elementwise_op!(Add, add, +);
+elementwise_op!(Sub, sub, -);
+elementwise_op!(Mul, mul, *);
+elementwise_op!(Div, div, /);
+
+#[test]
+fn arithmetic() {
+    let a = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = a.transpose();
+    assert_eq!(b.shape(), (3, 2));
+    assert_eq!(b.row(0), [1.0, 4.0]);
+    assert_eq!(a.col(2).copied().collect::<Vec<_>>(), [3.0, 6.0]);
+
+    assert_eq!(
+        a.matmul(&b),
+        Matrix::new(2, 2, vec![14.0, 32.0, 32.0, 77.0])
+    );
+    assert_eq!(a.matmul(&Matrix::identity(3)), a);
+    assert_eq!(a.matvec(&[1.0, 0.0, -1.0]), [-2.0, -2.0]);
+
+    assert_eq!(&(&a + &a) - &a, a);
+    assert_eq!((&a * 2.0).as_slice(), [2.0, 4.0, 6.0, 8.0, 10.0, 12.0]);
+    assert_eq!(
+        a.sub_row(&[1.0, 2.0, 3.0]).div_row(&[3.0, 3.0, 3.0]),
+        Matrix::new(2, 3, vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0])
+    );
+    assert_eq!(a.col_sums(), [5.0, 7.0, 9.0]);
+}
@@ -0,0 +1,322 @@
+use crate::datasets::ClassLabel;
+use crate::matrix::Matrix;
+
+/// How per-class scores are combined into a single score.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Average {
+    /// Computed from the total true positives, false negatives and false positives.
+    Micro,
+
+    /// Unweighted mean of the per-class scores.
+    Macro,
+
+    /// Mean of the per-class scores, weighted by the support of each class.
+    Weighted,
+}
+
+/// Precision, recall and F1 score of a single class.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClassScores {
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+
+    /// Number of samples of the class in the ground truth.
+    pub support: usize,
+}
+
+fn check_lengths<L>(y_true: &[L], y_pred: &[L]) {
+    assert_eq!(y_true.len(), y_pred.len(), "Inconsistent number of samples");
+    assert!(!y_true.is_empty(), "No samples");
+}
+
+/// Returns `numerator / denominator`, or 0 when the denominator is 0.
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator == 0.0 {
+        0.0
+    } else {
+        numerator / denominator
+    }
+}
+
+fn f1(precision: f64, recall: f64) -> f64 {
+    ratio(2.0 * precision * recall, precision + recall)
+}
+
+/// Fraction of the predictions that are correct.
+pub fn accuracy<L: ClassLabel>(y_true: &[L], y_pred: &[L]) -> f64 {
+    check_lengths(y_true, y_pred);
+    let correct = y_true.iter().zip(y_pred).filter(|(t, p)| t == p).count();
+    correct as f64 / y_true.len() as f64
+}
+
+/// Mean recall over the classes present in `y_true`, which accounts for imbalanced classes.
+pub fn balanced_accuracy<L: ClassLabel>(y_true: &[L], y_pred: &[L]) -> f64 {
+    let scores: Vec<ClassScores> = class_scores(y_true, y_pred)
+        .into_iter()
+        .filter(|s| s.support > 0)
+        .collect();
+    scores.iter().map(|s| s.recall).sum::<f64>() / scores.len() as f64
+}
+
+/// Counts of each `(true class, predicted class)` pair, with true classes as rows and predicted
+/// classes as columns, both in [ClassLabel::index] order.
+pub fn confusion_matrix<L: ClassLabel>(y_true: &[L], y_pred: &[L]) -> Matrix<usize> {
+    check_lengths(y_true, y_pred);
+    let num_classes = y_true
+        .iter()
+        .chain(y_pred)
+        .map(|label| label.index() + 1)
+        .max()
+        .unwrap();
+
+    let mut matrix = Matrix::zeros(num_classes, num_classes);
+    for (t, p) in y_true.iter().zip(y_pred) {
+        matrix[(t.index(), p.index())] += 1;
+    }
+    matrix
+}
+
+/// Precision, recall and F1 score of each class, in [ClassLabel::index] order.
+///
+/// Scores whose denominator is zero (e.g. the precision of a class that is never predicted) are
+/// reported as 0.
+pub fn class_scores<L: ClassLabel>(y_true: &[L], y_pred: &[L]) -> Vec<ClassScores> {
+    let matrix = confusion_matrix(y_true, y_pred);
+    let predicted = matrix.col_sums();
+
+    matrix
+        .rows()
+        .enumerate()
+        .map(|(k, row)| {
+            let true_positives = row[k] as f64;
+            let support: usize = row.iter().sum();
+            let precision = ratio(true_positives, predicted[k] as f64);
+            let recall = ratio(true_positives, support as f64);
+            ClassScores {
+                precision,
+                recall,
+                f1: f1(precision, recall),
+                support,
+            }
+        })
+        .collect()
+}
+
+/// Precision, recall and F1 score averaged over the classes that appear in `y_true` or
+/// `y_pred`.
+pub fn averaged_scores<L: ClassLabel>(y_true: &[L], y_pred: &[L], average: Average) -> ClassScores {
+    let predicted = confusion_matrix(y_true, y_pred).col_sums();
+    let scores: Vec<ClassScores> = class_scores(y_true, y_pred)
+        .into_iter()
+        .zip(predicted)
+        .filter(|(s, predicted)| s.support > 0 || *predicted > 0)
+        .map(|(s, _)| s)
+        .collect();
+    let support = y_true.len();
+
+    let weighted_mean = |weight: &dyn Fn(&ClassScores) -> f64| {
+        let total: f64 = scores.iter().map(weight).sum();
+        let mean = |score: fn(&ClassScores) -> f64| {
+            ratio(scores.iter().map(|s| score(s) * weight(s)).sum(), total)
+        };
+        ClassScores {
+            precision: mean(|s| s.precision),
+            recall: mean(|s| s.recall),
+            f1: mean(|s| s.f1),
+            support,
+        }
+    };
+
+    match average {
+        // Every misclassified sample is a false positive for one class and a false negative for
+        // another, so micro-averaged precision and recall both equal the accuracy.
+        Average::Micro => {
+            let accuracy = accuracy(y_true, y_pred);
+            ClassScores {
+                precision: accuracy,
+                recall: accuracy,
+                f1: accuracy,
+                support,
+            }
+        }
+        Average::Macro => weighted_mean(&|_| 1.0),
+        Average::Weighted => weighted_mean(&|s| s.support as f64),
+    }
+}
+
+pub fn precision<L: ClassLabel>(y_true: &[L], y_pred: &[L], average: Average) -> f64 {
+    averaged_scores(y_true, y_pred, average).precision
+}
+
+pub fn recall<L: ClassLabel>(y_true: &[L], y_pred: &[L], average: Average) -> f64 {
+    averaged_scores(y_true, y_pred, average).recall
+}
+
+pub fn f1_score<L: ClassLabel>(y_true: &[L], y_pred: &[L], average: Average) -> f64 {
+    averaged_scores(y_true, y_pred, average).f1
+}
+
+/// Returns the number of samples, the correct predictions, and the true and predicted count of
+/// each class.
+fn marginals<L: ClassLabel>(y_true: &[L], y_pred: &[L]) -> (f64, f64, Vec<f64>, Vec<f64>) {
+    let matrix = confusion_matrix(y_true, y_pred);
+    let correct = (0..matrix.num_rows())
+        .map(|k| matrix[(k, k)])
+        .sum::<usize>();
+    let actual = matrix.rows().map(|row| row.iter().sum::<usize>() as f64);
+    let predicted = matrix.col_sums().into_iter().map(|count| count as f64);
+
+    (
+        y_true.len() as f64,
+        correct as f64,
+        actual.collect(),
+        predicted.collect(),
+    )
+}
+
+/// Matthews correlation coefficient (multiclass generalization), between -1 and 1.
+pub fn matthews_corrcoef<L: ClassLabel>(y_true: &[L], y_pred: &[L]) -> f64 {
+    let (n, correct, actual, predicted) = marginals(y_true, y_pred);
+    let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>();
+
+    let covariance = correct * n - dot(&actual, &predicted);
+    let denominator =
+        ((n * n - dot(&predicted, &predicted)) * (n * n - dot(&actual, &actual))).sqrt();
+    ratio(covariance, denominator)
+}
+
+/// Cohen's kappa: agreement between `y_true` and `y_pred`, corrected for chance agreement.
+pub fn cohen_kappa<L: ClassLabel>(y_true: &[L], y_pred: &[L]) -> f64 {
+    let (n, correct, actual, predicted) = marginals(y_true, y_pred);
+
+    let observed = correct / n;
+    let expected = actual
+        .iter()
+        .zip(&predicted)
+        .map(|(a, p)| a * p)
+        .sum::<f64>()
+        / (n * n);
+    ratio(observed - expected, 1.0 - expected)
+}
+
+/// Text report of the per-class and averaged scores, laid out like scikit-learn's.
+///
+/// Classes are named after `target_names`, typically the `TARGET_NAMES` of the dataset.
+pub fn classification_report<L: ClassLabel>(
+    y_true: &[L],
+    y_pred: &[L],
+    target_names: &[&str],
+) -> String {
+    let scores = class_scores(y_true, y_pred);
+    assert!(scores.len() <= target_names.len(), "Missing target names");
+
+    let width = target_names
+        .iter()
+        .map(|name| name.len())
+        .chain(["weighted avg".len()])
+        .max()
+        .unwrap();
+    let row = |name: &str, s: &ClassScores| {
+        format!(
+            "{name:>width$}  {:>9.2} {:>9.2} {:>9.2} {:>9}\n",
+            s.precision, s.recall, s.f1, s.support
+        )
+    };
+
+    let mut report = format!(
+        "{:>width$}  {:>9} {:>9} {:>9} {:>9}\n\n",
+        "", "precision", "recall", "f1-score", "support"
+    );
+    for (k, name) in target_names.iter().enumerate() {
+        let class = scores.get(k).copied().unwrap_or(ClassScores {
+            precision: 0.0,
+            recall: 0.0,
+            f1: 0.0,
+            support: 0,
+        });
+        report += &row(name, &class);
+    }
+    report += &format!(
+        "\n{:>width$}  {:>9} {:>9} {:>9.2} {:>9}\n",
+        "accuracy",
+        "",
+        "",
+        accuracy(y_true, y_pred),
+        y_true.len()
+    );
+    report += &row(
+        "macro avg",
+        &averaged_scores(y_true, y_pred, Average::Macro),
+    );
+    report += &row(
+        "weighted avg",
+        &averaged_scores(y_true, y_pred, Average::Weighted),
+    );
+    report
+}
+
+#[test]
+fn averaged_scores_match_sklearn() {
+    // Example from the documentation of `sklearn.metrics.precision_recall_fscore_support`.
+    let y_true: [u8; 6] = [0, 1, 2, 0, 1, 2];
+    let y_pred: [u8; 6] = [0, 2, 1, 0, 0, 1];
+
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+    let macro_avg = averaged_scores(&y_true, &y_pred, Average::Macro);
+    assert!(close(macro_avg.precision, 2.0 / 9.0));
+    assert!(close(macro_avg.recall, 1.0 / 3.0));
+    assert!(close(macro_avg.f1, 4.0 / 15.0));
+    assert!(close(f1_score(&y_true, &y_pred, Average::Micro), 1.0 / 3.0));
+    assert!(close(
+        precision(&y_true, &y_pred, Average::Weighted),
+        2.0 / 9.0
+    ));
+
+    assert_eq!(
+        confusion_matrix(&y_true, &y_pred),
+        Matrix::new(3, 3, vec![2, 0, 0, 1, 0, 1, 0, 2, 0])
+    );
+    assert!(close(balanced_accuracy(&y_true, &y_pred), 1.0 / 3.0));
+
+    // Example from the documentation of `sklearn.metrics.matthews_corrcoef`.
+    let y_true: [u8; 4] = [1, 1, 1, 0];
+    let y_pred: [u8; 4] = [1, 0, 1, 1];
+    assert!(close(matthews_corrcoef(&y_true, &y_pred), -1.0 / 3.0));
+    assert!(close(cohen_kappa(&y_true, &y_pred), -1.0 / 3.0));
+}
+
+#[test]
+fn report_uses_target_names() {
+    use crate::datasets::iris::{Iris, Species};
+
+    let y_true = [
+        Species::IrisSetosa,
+        Species::IrisSetosa,
+        Species::IrisVersicolor,
+        Species::IrisVirginica,
+        Species::IrisVirginica,
+    ];
+    let y_pred = [
+        Species::IrisSetosa,
+        Species::IrisVersicolor,
+        Species::IrisVersicolor,
+        Species::IrisVirginica,
+        Species::IrisSetosa,
+    ];
+
+    assert_eq!(
+        classification_report(&y_true, &y_pred, &Iris::TARGET_NAMES),
+        concat!(
+            "                 precision    recall  f1-score   support\n",
+            "\n",
+            "    Iris setosa       0.50      0.50      0.50         2\n",
+            "Iris versicolor       0.50      1.00      0.67         1\n",
+            " Iris virginica       1.00      0.50      0.67         2\n",
+            "\n",
+            "       accuracy                           0.60         5\n",
+            "      macro avg       0.67      0.67      0.61         5\n",
+            "   weighted avg       0.70      0.60      0.60         5\n",
+        )
+    );
+}
@@ -0,0 +1,66 @@
+/// Number of pairs among `n` items.
+fn pairs(n: usize) -> f64 {
+    (n * n.saturating_sub(1)) as f64 / 2.0
+}
+
+/// Similarity of two clusterings of the same samples, corrected for chance: 1 for identical
+/// clusterings (up to the numbering of the clusters), close to 0 for independent ones, and
+/// negative for clusterings that disagree more than chance.
+///
+/// Class labels can be compared through their [ClassLabel::index](crate::datasets::ClassLabel).
+pub fn adjusted_rand_score(labels_true: &[usize], labels_pred: &[usize]) -> f64 {
+    assert_eq!(
+        labels_true.len(),
+        labels_pred.len(),
+        "Inconsistent number of samples"
+    );
+    let rows = labels_true.iter().max().map_or(0, |&k| k + 1);
+    let cols = labels_pred.iter().max().map_or(0, |&k| k + 1);
+    let mut contingency = vec![0; rows * cols];
+    for (&t, &p) in labels_true.iter().zip(labels_pred) {
+        contingency[t * cols + p] += 1;
+    }
+
+    let together: f64 = contingency.iter().map(|&n| pairs(n)).sum();
+    let true_pairs: f64 = contingency
+        .chunks(cols.max(1))
+        .map(|row| pairs(row.iter().sum()))
+        .sum();
+    let pred_pairs: f64 = (0..cols)
+        .map(|j| pairs((0..rows).map(|i| contingency[i * cols + j]).sum()))
+        .sum();
+
+    let expected = true_pairs * pred_pairs / pairs(labels_true.len()).max(1.0);
+    let maximum = (true_pairs + pred_pairs) / 2.0;
+    if maximum == expected {
+        // Both clusterings put every sample in its own cluster, or all in the same one.
+        return 1.0;
+    }
+    (together - expected) / (maximum - expected)
+}
+
+#[test]
+fn adjusted_rand_score_matches_sklearn() {
+    // Examples from the documentation of `sklearn.metrics.adjusted_rand_score`.
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+    assert!(close(
+        adjusted_rand_score(&[0, 0, 1, 1], &[1, 1, 0, 0]),
+        1.0
+    ));
+    assert!(close(
+        adjusted_rand_score(&[0, 0, 1, 2], &[0, 0, 1, 1]),
+        4.0 / 7.0
+    ));
+    assert!(close(
+        adjusted_rand_score(&[0, 0, 1, 1], &[0, 0, 1, 2]),
+        4.0 / 7.0
+    ));
+    assert!(close(
+        adjusted_rand_score(&[0, 0, 0, 0], &[0, 1, 2, 3]),
+        0.0
+    ));
+    assert!(close(
+        adjusted_rand_score(&[0, 0, 1, 1], &[0, 1, 0, 1]),
+        -0.5
+    ));
+}
@@ -0,0 +1,3 @@
+pub mod classification;
+pub mod clustering;
+pub mod regression;
@@ -0,0 +1,224 @@
+/// How the scores of the outputs of a multi-output regression are combined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultiOutput {
+    /// One score per output.
+    RawValues,
+
+    /// Unweighted mean of the scores.
+    UniformAverage,
+
+    /// Mean of the scores, weighted by the variance of each output in the ground truth.
+    VarianceWeighted,
+}
+
+fn to_f64<T: Copy + Into<f64>>(values: &[T]) -> Vec<f64> {
+    values.iter().map(|&v| v.into()).collect()
+}
+
+/// Returns `y_true - y_pred` for each sample.
+fn residuals<T: Copy + Into<f64>, P: Copy + Into<f64>>(y_true: &[T], y_pred: &[P]) -> Vec<f64> {
+    assert_eq!(y_true.len(), y_pred.len(), "Inconsistent number of samples");
+    assert!(!y_true.is_empty(), "No samples");
+    y_true
+        .iter()
+        .zip(y_pred)
+        .map(|(&t, &p)| t.into() - p.into())
+        .collect()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn variance(values: &[f64]) -> f64 {
+    let mean = mean(values);
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+}
+
+/// `1 - numerator / denominator`, with the degenerate cases of a constant ground truth mapped
+/// to 1 (perfect predictions) or 0 (imperfect ones) instead of infinities.
+fn fraction_explained(numerator: f64, denominator: f64) -> f64 {
+    match (numerator == 0.0, denominator == 0.0) {
+        (true, _) => 1.0,
+        (false, true) => 0.0,
+        (false, false) => 1.0 - numerator / denominator,
+    }
+}
+
+pub fn mean_squared_error<T: Copy + Into<f64>, P: Copy + Into<f64>>(
+    y_true: &[T],
+    y_pred: &[P],
+) -> f64 {
+    let residuals = residuals(y_true, y_pred);
+    residuals.iter().map(|r| r * r).sum::<f64>() / residuals.len() as f64
+}
+
+pub fn root_mean_squared_error<T: Copy + Into<f64>, P: Copy + Into<f64>>(
+    y_true: &[T],
+    y_pred: &[P],
+) -> f64 {
+    mean_squared_error(y_true, y_pred).sqrt()
+}
+
+pub fn mean_absolute_error<T: Copy + Into<f64>, P: Copy + Into<f64>>(
+    y_true: &[T],
+    y_pred: &[P],
+) -> f64 {
+    let residuals = residuals(y_true, y_pred);
+    residuals.iter().map(|r| r.abs()).sum::<f64>() / residuals.len() as f64
+}
+
+pub fn median_absolute_error<T: Copy + Into<f64>, P: Copy + Into<f64>>(
+    y_true: &[T],
+    y_pred: &[P],
+) -> f64 {
+    let mut errors: Vec<f64> = residuals(y_true, y_pred).iter().map(|r| r.abs()).collect();
+    errors.sort_by(f64::total_cmp);
+
+    let middle = errors.len() / 2;
+    if errors.len().is_multiple_of(2) {
+        (errors[middle - 1] + errors[middle]) / 2.0
+    } else {
+        errors[middle]
+    }
+}
+
+/// Largest absolute error over all samples.
+pub fn max_error<T: Copy + Into<f64>, P: Copy + Into<f64>>(y_true: &[T], y_pred: &[P]) -> f64 {
+    residuals(y_true, y_pred)
+        .iter()
+        .fold(0.0, |max, r| max.max(r.abs()))
+}
+
+/// Mean of the absolute errors relative to the true values.
+///
+/// True values of zero are replaced by `f64::EPSILON`, so the error of those samples is huge
+/// rather than infinite.
+pub fn mean_absolute_percentage_error<T: Copy + Into<f64>, P: Copy + Into<f64>>(
+    y_true: &[T],
+    y_pred: &[P],
+) -> f64 {
+    let relative: Vec<f64> = residuals(y_true, y_pred)
+        .iter()
+        .zip(to_f64(y_true))
+        .map(|(r, t)| r.abs() / t.abs().max(f64::EPSILON))
+        .collect();
+    mean(&relative)
+}
+
+/// Coefficient of determination: 1 for perfect predictions, 0 for always predicting the mean of
+/// `y_true`, and negative for worse predictions.
+pub fn r2_score<T: Copy + Into<f64>, P: Copy + Into<f64>>(y_true: &[T], y_pred: &[P]) -> f64 {
+    let residuals = residuals(y_true, y_pred);
+    let y_true = to_f64(y_true);
+
+    let residual_sum: f64 = residuals.iter().map(|r| r * r).sum();
+    let total_sum = variance(&y_true) * y_true.len() as f64;
+    fraction_explained(residual_sum, total_sum)
+}
+
+/// Like [r2_score], but ignores a constant bias of the predictions.
+pub fn explained_variance_score<T: Copy + Into<f64>, P: Copy + Into<f64>>(
+    y_true: &[T],
+    y_pred: &[P],
+) -> f64 {
+    let residuals = residuals(y_true, y_pred);
+    fraction_explained(variance(&residuals), variance(&to_f64(y_true)))
+}
+
+/// Scores each output of a multi-output regression with `metric` (e.g. [r2_score]) and combines
+/// the scores as requested by `multioutput`.
+///
+/// Returns one score per output for [MultiOutput::RawValues], and a single score otherwise.
+pub fn multi_output<T, P, const N: usize>(
+    metric: fn(&[f64], &[f64]) -> f64,
+    y_true: &[[T; N]],
+    y_pred: &[[P; N]],
+    multioutput: MultiOutput,
+) -> Vec<f64>
+where
+    T: Copy + Into<f64>,
+    P: Copy + Into<f64>,
+{
+    assert_eq!(y_true.len(), y_pred.len(), "Inconsistent number of samples");
+
+    let column = |rows: &[[f64; N]], j: usize| rows.iter().map(|row| row[j]).collect::<Vec<_>>();
+    let y_true: Vec<[f64; N]> = y_true.iter().map(|row| row.map(Into::into)).collect();
+    let y_pred: Vec<[f64; N]> = y_pred.iter().map(|row| row.map(Into::into)).collect();
+
+    let scores: Vec<f64> = (0..N)
+        .map(|j| metric(&column(&y_true, j), &column(&y_pred, j)))
+        .collect();
+    let weights: Vec<f64> = match multioutput {
+        MultiOutput::RawValues => return scores,
+        MultiOutput::UniformAverage => vec![1.0; N],
+        MultiOutput::VarianceWeighted => (0..N).map(|j| variance(&column(&y_true, j))).collect(),
+    };
+
+    let total: f64 = weights.iter().sum();
+    if total == 0.0 {
+        // Every output is constant, so there is nothing to weight by.
+        return vec![mean(&scores)];
+    }
+    vec![scores.iter().zip(&weights).map(|(s, w)| s * w).sum::<f64>() / total]
+}
+
+#[test]
+fn scores_match_sklearn() {
+    // Examples from the documentation of `sklearn.metrics`.
+    let y_true = [3.0, -0.5, 2.0, 7.0];
+    let y_pred = [2.5, 0.0, 2.0, 8.0];
+
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+    assert!(close(mean_squared_error(&y_true, &y_pred), 0.375));
+    assert!(close(
+        root_mean_squared_error(&y_true, &y_pred),
+        0.375f64.sqrt()
+    ));
+    assert!(close(mean_absolute_error(&y_true, &y_pred), 0.5));
+    assert!(close(median_absolute_error(&y_true, &y_pred), 0.5));
+    assert!(close(max_error(&y_true, &y_pred), 1.0));
+    assert!(close(r2_score(&y_true, &y_pred), 0.9486081370449679));
+    assert!(close(
+        explained_variance_score(&y_true, &y_pred),
+        0.9571734475374732
+    ));
+    assert!(close(
+        mean_absolute_percentage_error(&y_true, &y_pred),
+        0.3273809523809524
+    ));
+
+    let y_true = [[0.5, 1.0], [-1.0, 1.0], [7.0, -6.0]];
+    let y_pred = [[0.0, 2.0], [-1.0, 2.0], [8.0, -5.0]];
+    let mse = multi_output(mean_squared_error, &y_true, &y_pred, MultiOutput::RawValues);
+    assert!(close(mse[0], 0.4166666666666667) && close(mse[1], 1.0));
+    let r2 = multi_output(r2_score, &y_true, &y_pred, MultiOutput::VarianceWeighted);
+    assert!(close(r2[0], 0.9382566585956417));
+}
+
+#[test]
+fn linnerud_mean_predictor() {
+    use crate::datasets::{linnerud, Dataset};
+
+    let y_true: Vec<[i32; 3]> = linnerud::DATA.iter().map(Dataset::target).collect();
+    let means = [0, 1, 2].map(|j| y_true.iter().map(|y| y[j] as f32).sum::<f32>() / 20.0);
+    let y_pred = vec![means; y_true.len()];
+
+    for r2 in multi_output(r2_score, &y_true, &y_pred, MultiOutput::RawValues) {
+        assert!(r2.abs() < 1e-6);
+    }
+    let raw = multi_output(
+        mean_absolute_error,
+        &y_true,
+        &y_pred,
+        MultiOutput::RawValues,
+    );
+    let mae = multi_output(
+        mean_absolute_error,
+        &y_true,
+        &y_pred,
+        MultiOutput::UniformAverage,
+    );
+    assert_eq!(mae.len(), 1);
+    assert!((mae[0] - raw.iter().sum::<f64>() / 3.0).abs() < 1e-12);
+}
@@ -0,0 +1,304 @@
+use super::{group_by_target, TestSize};
+use crate::datasets::Dataset;
+use crate::utils::random::Rng;
+
+/// A strategy to repeatedly split a dataset into training and test sets.
+pub trait CrossValidator {
+    /// Returns an iterator over the `(train, test)` index sets of each split of `data`.
+    fn split<T: Dataset>(&self, data: &[T]) -> Splits;
+}
+
+/// Iterator over the `(train, test)` index sets of a [CrossValidator].
+///
+/// The training set of each split holds every index that is not in its test set, in increasing
+/// order.
+#[derive(Clone, Debug)]
+pub struct Splits {
+    num_samples: usize,
+    tests: std::vec::IntoIter<Vec<usize>>,
+}
+
+impl Splits {
+    fn new(num_samples: usize, tests: Vec<Vec<usize>>) -> Self {
+        Self {
+            num_samples,
+            tests: tests.into_iter(),
+        }
+    }
+}
+
+impl Iterator for Splits {
+    type Item = (Vec<usize>, Vec<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let test = self.tests.next()?;
+
+        let mut in_test = vec![false; self.num_samples];
+        for &i in &test {
+            in_test[i] = true;
+        }
+        let train = (0..self.num_samples).filter(|&i| !in_test[i]).collect();
+
+        Some((train, test))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.tests.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Splits {}
+
+/// Splits `indices` into `num_folds` consecutive folds, the first ones holding one more index
+/// when they cannot all have the same size.
+fn consecutive_folds(indices: &[usize], num_folds: usize) -> Vec<Vec<usize>> {
+    let (size, extra) = (indices.len() / num_folds, indices.len() % num_folds);
+    let mut start = 0;
+    (0..num_folds)
+        .map(|fold| {
+            let end = start + size + usize::from(fold < extra);
+            let mut test = indices[start..end].to_vec();
+            test.sort();
+            start = end;
+            test
+        })
+        .collect()
+}
+
+fn check_num_folds(num_folds: usize, num_samples: usize) {
+    assert!(num_folds >= 2, "At least two folds are needed");
+    assert!(
+        num_folds <= num_samples,
+        "Cannot have more folds than samples"
+    );
+}
+
+/// K-fold cross-validation: each sample is in the test set of exactly one of `k` folds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KFold {
+    num_folds: usize,
+    seed: Option<u64>,
+}
+
+impl KFold {
+    /// Folds of consecutive samples, in dataset order.
+    pub fn new(num_folds: usize) -> Self {
+        Self {
+            num_folds,
+            seed: None,
+        }
+    }
+
+    /// Shuffles the samples with `seed` before splitting them into folds.
+    pub fn shuffled(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl CrossValidator for KFold {
+    fn split<T: Dataset>(&self, data: &[T]) -> Splits {
+        check_num_folds(self.num_folds, data.len());
+
+        let mut indices: Vec<usize> = (0..data.len()).collect();
+        if let Some(seed) = self.seed {
+            Rng::new(seed).shuffle(&mut indices);
+        }
+        Splits::new(data.len(), consecutive_folds(&indices, self.num_folds))
+    }
+}
+
+/// K-fold cross-validation where each fold preserves the proportion of each target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StratifiedKFold {
+    num_folds: usize,
+    seed: Option<u64>,
+}
+
+impl StratifiedKFold {
+    /// Folds drawn from the samples of each target in dataset order.
+    pub fn new(num_folds: usize) -> Self {
+        Self {
+            num_folds,
+            seed: None,
+        }
+    }
+
+    /// Shuffles the samples of each target with `seed` before splitting them into folds.
+    pub fn shuffled(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl CrossValidator for StratifiedKFold {
+    fn split<T: Dataset>(&self, data: &[T]) -> Splits {
+        check_num_folds(self.num_folds, data.len());
+
+        let mut rng = self.seed.map(Rng::new);
+        let mut tests = vec![Vec::new(); self.num_folds];
+
+        // Dealing the samples of every target in turn, without restarting at the first fold for
+        // each target, keeps both the proportions and the sizes of the folds balanced.
+        let mut fold = 0;
+        for mut group in group_by_target(data) {
+            if let Some(rng) = rng.as_mut() {
+                rng.shuffle(&mut group);
+            }
+            for i in group {
+                tests[fold].push(i);
+                fold = (fold + 1) % self.num_folds;
+            }
+        }
+
+        for test in &mut tests {
+            test.sort();
+        }
+        Splits::new(data.len(), tests)
+    }
+}
+
+/// Shuffled [KFold] repeated several times, with a different shuffle each time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RepeatedKFold {
+    num_folds: usize,
+    num_repeats: usize,
+    seed: u64,
+}
+
+impl RepeatedKFold {
+    pub fn new(num_folds: usize, num_repeats: usize, seed: u64) -> Self {
+        Self {
+            num_folds,
+            num_repeats,
+            seed,
+        }
+    }
+}
+
+impl CrossValidator for RepeatedKFold {
+    fn split<T: Dataset>(&self, data: &[T]) -> Splits {
+        check_num_folds(self.num_folds, data.len());
+
+        let mut rng = Rng::new(self.seed);
+        let mut tests = Vec::with_capacity(self.num_folds * self.num_repeats);
+        for _ in 0..self.num_repeats {
+            let mut indices: Vec<usize> = (0..data.len()).collect();
+            rng.shuffle(&mut indices);
+            tests.extend(consecutive_folds(&indices, self.num_folds));
+        }
+        Splits::new(data.len(), tests)
+    }
+}
+
+/// Cross-validation where each sample is, in turn, the only sample of the test set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LeaveOneOut;
+
+impl CrossValidator for LeaveOneOut {
+    fn split<T: Dataset>(&self, data: &[T]) -> Splits {
+        check_num_folds(data.len(), data.len());
+        Splits::new(data.len(), (0..data.len()).map(|i| vec![i]).collect())
+    }
+}
+
+/// Independent random splits, like repeated calls to
+/// [train_test_split](super::train_test_split) with different seeds. Unlike k-fold, test sets
+/// may overlap.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShuffleSplit {
+    num_splits: usize,
+    test_size: TestSize,
+    seed: u64,
+}
+
+impl ShuffleSplit {
+    pub fn new(num_splits: usize, test_size: impl Into<TestSize>, seed: u64) -> Self {
+        Self {
+            num_splits,
+            test_size: test_size.into(),
+            seed,
+        }
+    }
+}
+
+impl CrossValidator for ShuffleSplit {
+    fn split<T: Dataset>(&self, data: &[T]) -> Splits {
+        let num_test = self.test_size.of(data.len());
+
+        let mut rng = Rng::new(self.seed);
+        let tests = (0..self.num_splits)
+            .map(|_| {
+                let mut test = rng.sample(data.len(), num_test);
+                test.sort();
+                test
+            })
+            .collect();
+        Splits::new(data.len(), tests)
+    }
+}
+
+#[test]
+fn folds_partition_the_data() {
+    use crate::datasets::{linnerud, wine};
+
+    let validators: [(&str, Splits, usize); 3] = [
+        ("k-fold", KFold::new(3).split(&linnerud::DATA), 3),
+        (
+            "shuffled k-fold",
+            KFold::new(6).shuffled(3).split(&linnerud::DATA),
+            6,
+        ),
+        ("leave-one-out", LeaveOneOut.split(&linnerud::DATA), 20),
+    ];
+    for (name, splits, num_splits) in validators {
+        assert_eq!(splits.len(), num_splits, "{name}");
+
+        let mut seen = [0; 20];
+        for (train, test) in splits {
+            assert_eq!(train.len() + test.len(), 20, "{name}");
+            assert!(train.iter().all(|i| !test.contains(i)), "{name}");
+            test.iter().for_each(|&i| seen[i] += 1);
+        }
+        assert!(seen.iter().all(|&count| count == 1), "{name}");
+    }
+
+    let sizes: Vec<usize> = KFold::new(3)
+        .split(&linnerud::DATA)
+        .map(|(_, test)| test.len())
+        .collect();
+    assert_eq!(sizes, [7, 7, 6]);
+
+    assert_eq!(RepeatedKFold::new(5, 3, 0).split(&wine::DATA).count(), 15);
+    for (train, test) in ShuffleSplit::new(4, 0.25, 0).split(&wine::DATA) {
+        assert_eq!((train.len(), test.len()), (133, 45));
+    }
+}
+
+#[test]
+fn stratified_folds() {
+    use crate::datasets::{breast_cancer, wine};
+
+    // 59, 71 and 48 samples of each class.
+    for (train, test) in StratifiedKFold::new(5).shuffled(1).split(&wine::DATA) {
+        let count = |label| {
+            test.iter()
+                .filter(|&&i| wine::DATA[i].label == label)
+                .count()
+        };
+        assert!((11..=12).contains(&count(wine::Label::Class0)));
+        assert!((14..=15).contains(&count(wine::Label::Class1)));
+        assert!((9..=10).contains(&count(wine::Label::Class2)));
+        assert!((35..=36).contains(&test.len()));
+        assert_eq!(train.len() + test.len(), 178);
+    }
+
+    // 212 malignant and 357 benign samples.
+    for (_, test) in StratifiedKFold::new(10).split(&breast_cancer::DATA) {
+        let malignant = test
+            .iter()
+            .filter(|&&i| breast_cancer::DATA[i].label == breast_cancer::Label::Malignant)
+            .count();
+        assert!((21..=22).contains(&malignant));
+    }
+}
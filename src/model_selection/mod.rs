@@ -0,0 +1,28 @@
+use crate::datasets::Dataset;
+
+mod folds;
+mod split;
+
+pub use folds::{
+    CrossValidator, KFold, LeaveOneOut, RepeatedKFold, ShuffleSplit, Splits, StratifiedKFold,
+};
+pub use split::{train_test_split, train_test_split_indices, TestSize};
+
+/// Groups the indices of `data` by target, in order of first appearance of each target.
+fn group_by_target<T: Dataset>(data: &[T]) -> Vec<Vec<usize>> {
+    let mut targets: Vec<T::Target> = Vec::new();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+
+    for (i, record) in data.iter().enumerate() {
+        let target = record.target();
+        match targets.iter().position(|&t| t == target) {
+            Some(group) => groups[group].push(i),
+            None => {
+                targets.push(target);
+                groups.push(vec![i]);
+            }
+        }
+    }
+
+    groups
+}
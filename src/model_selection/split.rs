@@ -0,0 +1,158 @@
+use super::group_by_target;
+use crate::datasets::Dataset;
+use crate::utils::random::Rng;
+
+/// Size of the test set of a split.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TestSize {
+    /// Fraction of the samples, between 0 and 1. The number of test samples is rounded up.
+    Fraction(f32),
+
+    /// Absolute number of samples.
+    Count(usize),
+}
+
+impl TestSize {
+    /// Number of test samples out of `num_samples`.
+    pub fn of(&self, num_samples: usize) -> usize {
+        let count = match *self {
+            TestSize::Fraction(fraction) => {
+                assert!(0.0 < fraction && fraction < 1.0, "Invalid test fraction");
+                (fraction * num_samples as f32).ceil() as usize
+            }
+            TestSize::Count(count) => count,
+        };
+        assert!(
+            0 < count && count < num_samples,
+            "Test size must leave samples in both sets"
+        );
+        count
+    }
+}
+
+impl From<f32> for TestSize {
+    fn from(fraction: f32) -> Self {
+        TestSize::Fraction(fraction)
+    }
+}
+
+impl From<usize> for TestSize {
+    fn from(count: usize) -> Self {
+        TestSize::Count(count)
+    }
+}
+
+/// Randomly splits `data` into a training and a test set.
+///
+/// The same `seed` always gives the same split. When `stratify` is `true`, each target is
+/// represented in both sets in the same proportion as in `data`.
+pub fn train_test_split<T: Dataset>(
+    data: &[T],
+    test_size: impl Into<TestSize>,
+    seed: u64,
+    stratify: bool,
+) -> (Vec<T>, Vec<T>) {
+    let (train, test) = train_test_split_indices(data, test_size, seed, stratify);
+    (
+        train.iter().map(|&i| data[i]).collect(),
+        test.iter().map(|&i| data[i]).collect(),
+    )
+}
+
+/// Same as [train_test_split], but returns the indices of the samples of each set.
+pub fn train_test_split_indices<T: Dataset>(
+    data: &[T],
+    test_size: impl Into<TestSize>,
+    seed: u64,
+    stratify: bool,
+) -> (Vec<usize>, Vec<usize>) {
+    let num_test = test_size.into().of(data.len());
+    let mut rng = Rng::new(seed);
+
+    if !stratify {
+        let mut indices: Vec<usize> = (0..data.len()).collect();
+        rng.shuffle(&mut indices);
+        let train = indices.split_off(num_test);
+        return (train, indices);
+    }
+
+    let groups = group_by_target(data);
+    let counts = allocate(&groups.iter().map(Vec::len).collect::<Vec<_>>(), num_test);
+
+    let mut train = Vec::with_capacity(data.len() - num_test);
+    let mut test = Vec::with_capacity(num_test);
+    for (mut group, count) in groups.into_iter().zip(counts) {
+        rng.shuffle(&mut group);
+        test.extend_from_slice(&group[..count]);
+        train.extend_from_slice(&group[count..]);
+    }
+    rng.shuffle(&mut train);
+    rng.shuffle(&mut test);
+
+    (train, test)
+}
+
+/// Distributes `total` samples among groups proportionally to their `sizes`.
+///
+/// Each group first gets the integer part of its share, and the samples left over go to the
+/// groups with the largest fractional parts (ties broken by order).
+pub(super) fn allocate(sizes: &[usize], total: usize) -> Vec<usize> {
+    let num_samples: usize = sizes.iter().sum();
+    let shares: Vec<f64> = sizes
+        .iter()
+        .map(|&size| size as f64 * total as f64 / num_samples as f64)
+        .collect();
+
+    let mut counts: Vec<usize> = shares.iter().map(|share| share.floor() as usize).collect();
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|&a, &b| {
+        (shares[b] - counts[b] as f64).total_cmp(&(shares[a] - counts[a] as f64))
+    });
+
+    let remaining = total - counts.iter().sum::<usize>();
+    for &group in order.iter().take(remaining) {
+        counts[group] += 1;
+    }
+    counts
+}
+
+#[test]
+fn stratified_split() {
+    use crate::datasets::{breast_cancer, iris};
+
+    let (train, test) = train_test_split(iris::DATA, 0.2, 42, true);
+    assert_eq!((train.len(), test.len()), (120, 30));
+    for species in [
+        iris::Species::IrisSetosa,
+        iris::Species::IrisVersicolor,
+        iris::Species::IrisVirginica,
+    ] {
+        assert_eq!(test.iter().filter(|r| r.species == species).count(), 10);
+        assert_eq!(train.iter().filter(|r| r.species == species).count(), 40);
+    }
+
+    // 212 malignant and 357 benign samples.
+    let (train, test) = train_test_split(&breast_cancer::DATA, 100, 7, true);
+    let malignant = |set: &[breast_cancer::BreastCancer]| {
+        set.iter()
+            .filter(|r| r.label == breast_cancer::Label::Malignant)
+            .count()
+    };
+    assert_eq!((malignant(&train), malignant(&test)), (175, 37));
+    assert_eq!((train.len(), test.len()), (469, 100));
+}
+
+#[test]
+fn seeded_split() {
+    use crate::datasets::wine;
+
+    let split = train_test_split_indices(&wine::DATA, 0.25, 1, false);
+    assert_eq!(split, train_test_split_indices(&wine::DATA, 0.25, 1, false));
+    assert_ne!(split, train_test_split_indices(&wine::DATA, 0.25, 2, false));
+
+    let (train, test) = split;
+    assert_eq!((train.len(), test.len()), (133, 45));
+    let mut all: Vec<usize> = train.into_iter().chain(test).collect();
+    all.sort();
+    assert_eq!(all, (0..178).collect::<Vec<_>>());
+}
@@ -0,0 +1,320 @@
+use std::marker::PhantomData;
+
+use super::{check_lengths, check_priors, ClassCounts, NaiveBayes};
+use crate::datasets::ClassLabel;
+use crate::matrix::Matrix;
+
+/// Smallest smoothing actually applied, as in scikit-learn, so that a feature never seen in a
+/// class has a finite log probability.
+const MIN_ALPHA: f64 = 1e-10;
+
+/// Class counts and per-class sums of each feature.
+#[derive(Clone, Debug, PartialEq)]
+struct FeatureCounts {
+    counts: ClassCounts,
+    feature_counts: Vec<Vec<f64>>,
+}
+
+impl FeatureCounts {
+    fn new() -> Self {
+        Self {
+            counts: ClassCounts::default(),
+            feature_counts: Vec::new(),
+        }
+    }
+
+    /// Adds the rows of `x`, transformed by `value`, to the sums of their classes `y`.
+    fn add<L: ClassLabel>(&mut self, x: &Matrix, y: &[L], value: impl Fn(f32) -> f64) {
+        check_lengths(x, y.len());
+        let num_features = x.num_cols();
+        assert!(
+            self.feature_counts
+                .first()
+                .is_none_or(|counts| counts.len() == num_features),
+            "Invalid number of columns"
+        );
+
+        let classes = self.counts.add(y);
+        self.feature_counts
+            .resize(self.counts.len(), vec![0.0; num_features]);
+        for (row, k) in x.rows().zip(classes) {
+            for (count, &x) in self.feature_counts[k].iter_mut().zip(row) {
+                *count += value(x);
+            }
+        }
+    }
+}
+
+/// Naive Bayes for count features, e.g. word counts or pixel intensities, following a
+/// multinomial distribution within each class.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultinomialNB<L> {
+    alpha: f64,
+    priors: Option<Vec<f64>>,
+    fitted: Option<FeatureCounts>,
+    labels: PhantomData<L>,
+}
+
+impl<L: ClassLabel> MultinomialNB<L> {
+    pub fn new() -> Self {
+        Self {
+            alpha: 1.0,
+            priors: None,
+            fitted: None,
+            labels: PhantomData,
+        }
+    }
+
+    /// Additive (Laplace) smoothing of the feature counts (1 by default), so that a feature
+    /// never seen in a class does not rule the class out. Values below 1e-10 are raised to it.
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        assert!(alpha >= 0.0, "Invalid alpha");
+        self.alpha = alpha.max(MIN_ALPHA);
+        self
+    }
+
+    /// Prior probability of each class, instead of the empirical class distribution.
+    pub fn with_priors(mut self, priors: Vec<f64>) -> Self {
+        check_priors(&priors);
+        self.priors = Some(priors);
+        self
+    }
+
+    /// Smoothed log probability of each feature (columns) within each class (rows), once
+    /// fitted.
+    pub fn feature_log_prob(&self) -> Option<Matrix<f64>> {
+        let fitted = self.fitted.as_ref()?;
+        let rows: Vec<Vec<f64>> = fitted
+            .feature_counts
+            .iter()
+            .map(|counts| {
+                let total = counts.iter().sum::<f64>() + self.alpha * counts.len() as f64;
+                counts
+                    .iter()
+                    .map(|count| ((count + self.alpha) / total).ln())
+                    .collect()
+            })
+            .collect();
+        Some(Matrix::from_rows(&rows))
+    }
+}
+
+impl<L: ClassLabel> Default for MultinomialNB<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: ClassLabel> NaiveBayes for MultinomialNB<L> {
+    type Label = L;
+
+    fn fit(&mut self, x: &Matrix, y: &[L]) {
+        self.fitted = None;
+        self.partial_fit(x, y);
+    }
+
+    fn partial_fit(&mut self, x: &Matrix, y: &[L]) {
+        assert!(
+            x.as_slice().iter().all(|&v| v >= 0.0),
+            "Counts must be non-negative"
+        );
+        self.fitted
+            .get_or_insert_with(FeatureCounts::new)
+            .add(x, y, f64::from);
+    }
+
+    fn joint_log_likelihood(&self, x: &Matrix) -> Matrix<f64> {
+        let fitted = self.fitted.as_ref().expect("Model not fitted");
+        let log_priors = fitted.counts.log_priors(self.priors.as_deref());
+        let log_prob = self.feature_log_prob().unwrap();
+        assert_eq!(
+            x.num_cols(),
+            log_prob.num_cols(),
+            "Invalid number of columns"
+        );
+
+        x.map(f64::from)
+            .matmul(&log_prob.transpose())
+            .add_row(&log_priors)
+    }
+}
+
+/// Naive Bayes for binary features, each following a Bernoulli distribution within a class.
+///
+/// Unlike [MultinomialNB], the absence of a feature counts as evidence too.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BernoulliNB<L> {
+    alpha: f64,
+    binarize: Option<f32>,
+    priors: Option<Vec<f64>>,
+    fitted: Option<FeatureCounts>,
+    labels: PhantomData<L>,
+}
+
+impl<L: ClassLabel> BernoulliNB<L> {
+    pub fn new() -> Self {
+        Self {
+            alpha: 1.0,
+            binarize: Some(0.0),
+            priors: None,
+            fitted: None,
+            labels: PhantomData,
+        }
+    }
+
+    /// Additive (Laplace) smoothing of the feature counts (1 by default). Values below 1e-10
+    /// are raised to it.
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        assert!(alpha >= 0.0, "Invalid alpha");
+        self.alpha = alpha.max(MIN_ALPHA);
+        self
+    }
+
+    /// Threshold above which a feature is present (0 by default), or `None` if the features are
+    /// already 0 or 1.
+    pub fn with_binarize(mut self, threshold: Option<f32>) -> Self {
+        self.binarize = threshold;
+        self
+    }
+
+    /// Prior probability of each class, instead of the empirical class distribution.
+    pub fn with_priors(mut self, priors: Vec<f64>) -> Self {
+        check_priors(&priors);
+        self.priors = Some(priors);
+        self
+    }
+
+    /// Smoothed log probability that each feature (columns) is present within each class
+    /// (rows), once fitted.
+    pub fn feature_log_prob(&self) -> Option<Matrix<f64>> {
+        let fitted = self.fitted.as_ref()?;
+        let rows: Vec<Vec<f64>> = fitted
+            .feature_counts
+            .iter()
+            .zip(&fitted.counts.0)
+            .map(|(counts, n)| {
+                counts
+                    .iter()
+                    .map(|count| ((count + self.alpha) / (n + 2.0 * self.alpha)).ln())
+                    .collect()
+            })
+            .collect();
+        Some(Matrix::from_rows(&rows))
+    }
+
+    fn binarized(&self, x: f32) -> f64 {
+        match self.binarize {
+            Some(threshold) => f64::from(u8::from(x > threshold)),
+            None => x as f64,
+        }
+    }
+}
+
+impl<L: ClassLabel> Default for BernoulliNB<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: ClassLabel> NaiveBayes for BernoulliNB<L> {
+    type Label = L;
+
+    fn fit(&mut self, x: &Matrix, y: &[L]) {
+        self.fitted = None;
+        self.partial_fit(x, y);
+    }
+
+    fn partial_fit(&mut self, x: &Matrix, y: &[L]) {
+        let mut fitted = self.fitted.take().unwrap_or_else(FeatureCounts::new);
+        fitted.add(x, y, |v| self.binarized(v));
+        self.fitted = Some(fitted);
+    }
+
+    fn joint_log_likelihood(&self, x: &Matrix) -> Matrix<f64> {
+        let fitted = self.fitted.as_ref().expect("Model not fitted");
+        let log_priors = fitted.counts.log_priors(self.priors.as_deref());
+        let log_prob = self.feature_log_prob().unwrap();
+        assert_eq!(
+            x.num_cols(),
+            log_prob.num_cols(),
+            "Invalid number of columns"
+        );
+
+        // log P(row | class) = sum of x log p + (1 - x) log (1 - p)
+        //                    = sum of log (1 - p) + x (log p - log (1 - p))
+        let log_absent = log_prob.map(|log_p| (-log_p.exp()).ln_1p());
+        let x = x.map(|v| self.binarized(v));
+        let constant: Vec<f64> = log_absent
+            .rows()
+            .zip(&log_priors)
+            .map(|(row, log_prior)| log_prior + row.iter().sum::<f64>())
+            .collect();
+        x.matmul(&(&log_prob - &log_absent).transpose())
+            .add_row(&constant)
+    }
+}
+
+#[test]
+fn discrete_probabilities() {
+    let x = Matrix::new(2, 2, vec![2.0, 1.0, 0.0, 3.0]);
+    let y: [u8; 2] = [0, 1];
+
+    // Feature probabilities (3/5, 2/5) for class 0 and (1/5, 4/5) for class 1.
+    let mut nb = MultinomialNB::new();
+    nb.fit(&x, &y);
+    let proba = nb.predict_proba(&Matrix::new(1, 2, vec![1.0, 1.0]));
+    assert!((proba[(0, 0)] - 0.6).abs() < 1e-12 && (proba[(0, 1)] - 0.4).abs() < 1e-12);
+
+    // Presence probabilities (2/3, 2/3) for class 0 and (1/3, 2/3) for class 1.
+    let mut nb = BernoulliNB::new();
+    nb.fit(&x, &y);
+    let log_proba = nb.predict_log_proba(&Matrix::new(1, 2, vec![5.0, 0.0]));
+    assert!((log_proba[(0, 0)] - (2.0f64 / 3.0).ln()).abs() < 1e-12);
+
+    let mut nb = BernoulliNB::new().with_priors(vec![0.2, 0.8]);
+    nb.fit(&x, &y);
+    assert_eq!(nb.predict(&Matrix::new(1, 2, vec![5.0, 0.0])), [1]);
+}
+
+#[test]
+fn classifies_digit_pixels() {
+    use crate::datasets::digits::{self, Digits};
+    use crate::datasets::Dataset;
+    use crate::metrics::classification::accuracy;
+    use crate::model_selection::train_test_split;
+
+    let (train, test) = train_test_split(&digits::DATA, 0.25, 0, true);
+    let (x_train, y_train) = Digits::to_xy(&train);
+    let (x_test, y_test) = Digits::to_xy(&test);
+
+    let mut multinomial = MultinomialNB::new();
+    let mut bernoulli = BernoulliNB::new();
+    for start in (0..x_train.num_rows()).step_by(500) {
+        let batch: Vec<usize> = (start..(start + 500).min(x_train.num_rows())).collect();
+        let y_batch: Vec<u8> = batch.iter().map(|&i| y_train[i]).collect();
+        multinomial.partial_fit(&x_train.select_rows(&batch), &y_batch);
+        bernoulli.partial_fit(&x_train.select_rows(&batch), &y_batch);
+    }
+    assert!(accuracy(&y_test, &multinomial.predict(&x_test)) > 0.85);
+    assert!(accuracy(&y_test, &bernoulli.predict(&x_test)) > 0.8);
+}
+
+#[test]
+fn zero_alpha_on_digits() {
+    use crate::datasets::digits::{self, Digits};
+    use crate::datasets::Dataset;
+
+    // Pixel 0 is blank in every image, so it is never seen in any class.
+    let (x, y) = Digits::to_xy(&digits::DATA);
+    assert!(x.col(0).all(|&v| v == 0.0));
+    let mut multinomial = MultinomialNB::new().with_alpha(0.0);
+    let mut bernoulli = BernoulliNB::new().with_alpha(0.0);
+    multinomial.fit(&x, &y);
+    bernoulli.fit(&x, &y);
+    for log_proba in [
+        multinomial.predict_log_proba(&x),
+        bernoulli.predict_log_proba(&x),
+    ] {
+        assert!(log_proba.as_slice().iter().all(|v| v.is_finite()));
+    }
+}
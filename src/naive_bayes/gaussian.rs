@@ -0,0 +1,225 @@
+use std::f64::consts::PI;
+use std::marker::PhantomData;
+
+use super::{check_lengths, check_priors, ClassCounts, NaiveBayes};
+use crate::datasets::ClassLabel;
+use crate::matrix::Matrix;
+
+/// Naive Bayes for continuous features, each normally distributed within a class.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaussianNB<L> {
+    priors: Option<Vec<f64>>,
+    var_smoothing: f64,
+    fitted: Option<GaussianStats>,
+    labels: PhantomData<L>,
+}
+
+/// Running mean and sum of squared deviations of each feature within each class.
+#[derive(Clone, Debug, PartialEq)]
+struct GaussianStats {
+    counts: ClassCounts,
+    mean: Vec<Vec<f64>>,
+    m2: Vec<Vec<f64>>,
+
+    /// Added to every variance, so that constant features do not make the likelihood infinite.
+    epsilon: f64,
+}
+
+impl<L: ClassLabel> GaussianNB<L> {
+    pub fn new() -> Self {
+        Self {
+            priors: None,
+            var_smoothing: 1e-9,
+            fitted: None,
+            labels: PhantomData,
+        }
+    }
+
+    /// Prior probability of each class, instead of the empirical class distribution.
+    pub fn with_priors(mut self, priors: Vec<f64>) -> Self {
+        check_priors(&priors);
+        self.priors = Some(priors);
+        self
+    }
+
+    /// Fraction of the largest feature variance added to all variances (`1e-9` by default).
+    pub fn with_var_smoothing(mut self, var_smoothing: f64) -> Self {
+        assert!(var_smoothing >= 0.0, "Invalid variance smoothing");
+        self.var_smoothing = var_smoothing;
+        self
+    }
+
+    /// Mean of each feature (columns) within each class (rows), once fitted.
+    pub fn means(&self) -> Option<Matrix<f64>> {
+        let stats = self.fitted.as_ref()?;
+        Some(Matrix::from_rows(&stats.mean))
+    }
+
+    /// Smoothed variance of each feature (columns) within each class (rows), once fitted.
+    pub fn variances(&self) -> Option<Matrix<f64>> {
+        let stats = self.fitted.as_ref()?;
+        Some(Matrix::from_rows(&stats.variances()))
+    }
+}
+
+impl GaussianStats {
+    fn variances(&self) -> Vec<Vec<f64>> {
+        self.m2
+            .iter()
+            .zip(&self.counts.0)
+            .map(|(m2, &n)| {
+                m2.iter()
+                    .map(|m2| if n > 0.0 { m2 / n } else { 0.0 } + self.epsilon)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl<L: ClassLabel> Default for GaussianNB<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: ClassLabel> NaiveBayes for GaussianNB<L> {
+    type Label = L;
+
+    fn fit(&mut self, x: &Matrix, y: &[L]) {
+        self.fitted = None;
+        self.partial_fit(x, y);
+    }
+
+    fn partial_fit(&mut self, x: &Matrix, y: &[L]) {
+        check_lengths(x, y.len());
+        let num_features = x.num_cols();
+        let stats = self.fitted.get_or_insert_with(|| GaussianStats {
+            counts: ClassCounts::default(),
+            mean: Vec::new(),
+            m2: Vec::new(),
+            epsilon: 0.0,
+        });
+        assert!(
+            stats
+                .mean
+                .first()
+                .is_none_or(|mean| mean.len() == num_features),
+            "Invalid number of columns"
+        );
+
+        // Like scikit-learn, the smoothing follows the variance of the latest batch.
+        let max_variance = (0..num_features)
+            .map(|j| {
+                let column: Vec<f64> = x.col(j).map(|&v| v as f64).collect();
+                let mean = column.iter().sum::<f64>() / column.len() as f64;
+                column.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / column.len() as f64
+            })
+            .fold(0.0, f64::max);
+        stats.epsilon = if max_variance > 0.0 {
+            self.var_smoothing * max_variance
+        } else {
+            // Every feature is constant: smooth in absolute terms, so that no variance is zero.
+            self.var_smoothing.max(f64::EPSILON)
+        };
+
+        let mut seen = stats.counts.0.clone();
+        let classes = stats.counts.add(y);
+        seen.resize(stats.counts.len(), 0.0);
+        stats
+            .mean
+            .resize(stats.counts.len(), vec![0.0; num_features]);
+        stats.m2.resize(stats.counts.len(), vec![0.0; num_features]);
+
+        // Welford's online update, one sample at a time.
+        for (row, &k) in x.rows().zip(&classes) {
+            seen[k] += 1.0;
+            for (j, &value) in row.iter().enumerate() {
+                let value = value as f64;
+                let delta = value - stats.mean[k][j];
+                stats.mean[k][j] += delta / seen[k];
+                stats.m2[k][j] += delta * (value - stats.mean[k][j]);
+            }
+        }
+    }
+
+    fn joint_log_likelihood(&self, x: &Matrix) -> Matrix<f64> {
+        let stats = self.fitted.as_ref().expect("Model not fitted");
+        let log_priors = stats.counts.log_priors(self.priors.as_deref());
+        let variances = stats.variances();
+
+        let mut jll = Matrix::zeros(x.num_rows(), stats.counts.len());
+        for (i, row) in x.rows().enumerate() {
+            assert_eq!(row.len(), stats.mean[0].len(), "Invalid number of columns");
+            for k in 0..stats.counts.len() {
+                jll[(i, k)] = log_priors[k]
+                    + row
+                        .iter()
+                        .zip(stats.mean[k].iter().zip(&variances[k]))
+                        .map(|(&x, (mean, var))| {
+                            -0.5 * ((2.0 * PI * var).ln() + (x as f64 - mean).powi(2) / var)
+                        })
+                        .sum::<f64>();
+            }
+        }
+        jll
+    }
+}
+
+#[test]
+fn gaussian_matches_sklearn() {
+    use crate::datasets::iris::{self, Iris, Species};
+    use crate::datasets::Dataset;
+    use crate::metrics::classification::accuracy;
+
+    // Example from the documentation of `sklearn.naive_bayes.GaussianNB`.
+    let x = Matrix::new(
+        6,
+        2,
+        vec![-1., -1., -2., -1., -3., -2., 1., 1., 2., 1., 3., 2.],
+    );
+    let y: [u8; 6] = [1, 1, 1, 2, 2, 2];
+    let mut nb = GaussianNB::new();
+    nb.fit(&x, &y);
+    assert_eq!(nb.predict(&Matrix::new(1, 2, vec![-0.8, -1.0])), [1]);
+    // Class 0 never appears, so it is never predicted.
+    assert_eq!(nb.predict_proba(&x)[(0, 0)], 0.0);
+
+    let (x, y) = Iris::to_xy(iris::DATA);
+    let mut nb = GaussianNB::<Species>::new();
+    nb.fit(&x, &y);
+    assert!((accuracy(&y, &nb.predict(&x)) - 0.96).abs() < 1e-9);
+
+    // Fitting in batches gives the same statistics as fitting at once.
+    let mut batched = GaussianNB::<Species>::new();
+    let order: Vec<usize> = (0..150).map(|i| (i * 7) % 150).collect();
+    let (x, y): (Matrix, Vec<Species>) =
+        (x.select_rows(&order), order.iter().map(|&i| y[i]).collect());
+    for start in (0..150).step_by(40) {
+        let batch: Vec<usize> = (start..(start + 40).min(150)).collect();
+        let y_batch: Vec<Species> = batch.iter().map(|&i| y[i]).collect();
+        batched.partial_fit(&x.select_rows(&batch), &y_batch);
+    }
+    let (a, b) = (batched.means().unwrap(), nb.means().unwrap());
+    assert!(a
+        .as_slice()
+        .iter()
+        .zip(b.as_slice())
+        .all(|(a, b)| (a - b).abs() < 1e-9));
+    let (a, b) = (batched.variances().unwrap(), nb.variances().unwrap());
+    assert!(a
+        .as_slice()
+        .iter()
+        .zip(b.as_slice())
+        .all(|(a, b)| (a - b).abs() < 1e-6));
+}
+
+#[test]
+fn constant_features() {
+    let x = Matrix::new(4, 2, vec![1.0, 2.0, 1.0, 2.0, 1.0, 2.0, 1.0, 2.0]);
+    let y: [u8; 4] = [0, 0, 0, 1];
+    let mut nb = GaussianNB::new().with_var_smoothing(0.0);
+    nb.fit(&x, &y);
+    let proba = nb.predict_proba(&x);
+    assert!(proba.as_slice().iter().all(|p| p.is_finite()));
+    assert_eq!(nb.predict(&x), [0, 0, 0, 0]);
+}
@@ -0,0 +1,114 @@
+use crate::datasets::ClassLabel;
+use crate::matrix::Matrix;
+
+mod discrete;
+mod gaussian;
+
+pub use discrete::{BernoulliNB, MultinomialNB};
+pub use gaussian::GaussianNB;
+
+/// A classifier that applies Bayes' theorem with the assumption that features are independent
+/// given the class.
+///
+/// The classes are the labels up to the largest [ClassLabel::index] seen while fitting. Classes
+/// without any training sample have a probability of zero.
+pub trait NaiveBayes {
+    type Label: ClassLabel;
+
+    /// Fits the model to the rows of `x` and their labels `y`, discarding any previous fit.
+    fn fit(&mut self, x: &Matrix, y: &[Self::Label]);
+
+    /// Updates the model with another batch of rows and labels, which gives the same model as
+    /// fitting all of the batches at once.
+    fn partial_fit(&mut self, x: &Matrix, y: &[Self::Label]);
+
+    /// Returns the unnormalized log posterior `log P(class) + log P(row | class)` of each class
+    /// (columns, in [ClassLabel::index] order) for each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    fn joint_log_likelihood(&self, x: &Matrix) -> Matrix<f64>;
+
+    /// Returns the log probability of each class (columns) for each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    fn predict_log_proba(&self, x: &Matrix) -> Matrix<f64> {
+        let mut jll = self.joint_log_likelihood(x);
+        for i in 0..jll.num_rows() {
+            let row = jll.row_mut(i);
+            let max = row.iter().fold(f64::NEG_INFINITY, |max, &v| max.max(v));
+            let log_sum = max + row.iter().map(|v| (v - max).exp()).sum::<f64>().ln();
+            row.iter_mut().for_each(|v| *v -= log_sum);
+        }
+        jll
+    }
+
+    /// Returns the probability of each class (columns) for each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    fn predict_proba(&self, x: &Matrix) -> Matrix<f64> {
+        self.predict_log_proba(x).map(f64::exp)
+    }
+
+    /// Predicts the most probable class of each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    fn predict(&self, x: &Matrix) -> Vec<Self::Label> {
+        self.joint_log_likelihood(x)
+            .rows()
+            .map(|row| {
+                let best = row
+                    .iter()
+                    .enumerate()
+                    .fold(0, |best, (k, &v)| if v > row[best] { k } else { best });
+                Self::Label::from_index(best)
+            })
+            .collect()
+    }
+}
+
+/// Number of training samples of each class, grown as labels with higher indices show up.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ClassCounts(Vec<f64>);
+
+impl ClassCounts {
+    /// Adds the labels `y`, and returns their class indices.
+    fn add<L: ClassLabel>(&mut self, y: &[L]) -> Vec<usize> {
+        let classes: Vec<usize> = y.iter().map(ClassLabel::index).collect();
+        for &k in &classes {
+            if k >= self.0.len() {
+                self.0.resize(k + 1, 0.0);
+            }
+            self.0[k] += 1.0;
+        }
+        classes
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Log of `priors` if given, or of the empirical class distribution.
+    fn log_priors(&self, priors: Option<&[f64]>) -> Vec<f64> {
+        match priors {
+            Some(priors) => {
+                assert_eq!(priors.len(), self.len(), "Invalid number of priors");
+                priors.iter().map(|p| p.ln()).collect()
+            }
+            None => {
+                let total: f64 = self.0.iter().sum();
+                self.0.iter().map(|count| (count / total).ln()).collect()
+            }
+        }
+    }
+}
+
+fn check_priors(priors: &[f64]) {
+    assert!(
+        priors.iter().all(|&p| p >= 0.0) && (priors.iter().sum::<f64>() - 1.0).abs() < 1e-9,
+        "Priors must be non-negative and sum to 1"
+    );
+}
+
+fn check_lengths(x: &Matrix, y_len: usize) {
+    assert_eq!(x.num_rows(), y_len, "Inconsistent number of samples");
+}
@@ -0,0 +1,273 @@
+use std::marker::PhantomData;
+
+use super::Metric;
+use crate::datasets::{ClassLabel, Dataset};
+use crate::matrix::Matrix;
+
+/// How the neighbors of a sample contribute to its prediction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Weights {
+    /// Every neighbor counts the same (the default).
+    #[default]
+    Uniform,
+
+    /// Neighbors count by the inverse of their distance. Neighbors at distance zero, if any, are
+    /// the only ones that count.
+    Distance,
+}
+
+/// Brute-force neighbor search shared by the classifier and the regressor.
+#[derive(Clone, Debug, PartialEq)]
+struct Search {
+    num_neighbors: usize,
+    weights: Weights,
+    metric: Metric,
+    train: Option<Matrix>,
+}
+
+impl Search {
+    fn new(num_neighbors: usize) -> Self {
+        assert!(num_neighbors > 0, "At least one neighbor is needed");
+        Self {
+            num_neighbors,
+            weights: Weights::Uniform,
+            metric: Metric::Euclidean,
+            train: None,
+        }
+    }
+
+    fn fit(&mut self, train: Matrix) {
+        assert!(
+            self.num_neighbors <= train.num_rows(),
+            "Cannot have more neighbors than samples"
+        );
+        self.train = Some(train);
+    }
+
+    /// Returns the index in the training set and the distance of the nearest neighbors of each
+    /// row of `x`, from nearest to farthest. Ties go to the first training sample.
+    fn kneighbors(&self, x: &Matrix) -> Vec<Vec<(usize, f64)>> {
+        let train = self.train.as_ref().expect("Model not fitted");
+        x.rows()
+            .map(|query| {
+                let mut distances: Vec<(usize, f64)> = train
+                    .rows()
+                    .map(|row| self.metric.distance(query, row))
+                    .enumerate()
+                    .collect();
+                let by_distance =
+                    |a: &(usize, f64), b: &(usize, f64)| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0));
+                distances.select_nth_unstable_by(self.num_neighbors - 1, by_distance);
+                distances.truncate(self.num_neighbors);
+                distances.sort_by(by_distance);
+                distances
+            })
+            .collect()
+    }
+
+    /// Returns the weight of each of `neighbors` in the prediction.
+    fn weights(&self, neighbors: &[(usize, f64)]) -> Vec<f64> {
+        match self.weights {
+            Weights::Uniform => vec![1.0; neighbors.len()],
+            Weights::Distance if neighbors.iter().any(|&(_, d)| d == 0.0) => neighbors
+                .iter()
+                .map(|&(_, d)| if d == 0.0 { 1.0 } else { 0.0 })
+                .collect(),
+            Weights::Distance => neighbors.iter().map(|&(_, d)| d.recip()).collect(),
+        }
+    }
+}
+
+/// Classifies samples by a vote of their `k` nearest neighbors in the training set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KNeighborsClassifier<T> {
+    search: Search,
+    classes: Vec<usize>,
+    num_classes: usize,
+    records: PhantomData<T>,
+}
+
+impl<T: Dataset> KNeighborsClassifier<T>
+where
+    T::Target: ClassLabel,
+{
+    /// Classifier voting among `num_neighbors` neighbors, with the [Euclidean](Metric::Euclidean)
+    /// metric and [uniform](Weights::Uniform) weights.
+    pub fn new(num_neighbors: usize) -> Self {
+        Self {
+            search: Search::new(num_neighbors),
+            classes: Vec::new(),
+            num_classes: 0,
+            records: PhantomData,
+        }
+    }
+
+    pub fn with_weights(mut self, weights: Weights) -> Self {
+        self.search.weights = weights;
+        self
+    }
+
+    pub fn with_metric(mut self, metric: Metric) -> Self {
+        self.search.metric = metric;
+        self
+    }
+
+    /// Stores the features and targets of `records` as the training set.
+    pub fn fit(&mut self, records: &[T]) {
+        self.search.fit(T::to_matrix(records));
+        self.classes = records.iter().map(|r| r.target().index()).collect();
+        self.num_classes = self.classes.iter().max().map_or(0, |&k| k + 1);
+    }
+
+    /// Returns the index in the training set and the distance of the nearest neighbors of each
+    /// of `records`, from nearest to farthest.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn kneighbors(&self, records: &[T]) -> Vec<Vec<(usize, f64)>> {
+        self.search.kneighbors(&T::to_matrix(records))
+    }
+
+    /// Returns the weighted share of the votes of each class (columns, in [ClassLabel::index]
+    /// order) for each of `records`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict_proba(&self, records: &[T]) -> Matrix<f64> {
+        let neighbors = self.kneighbors(records);
+        let mut votes = Matrix::zeros(records.len(), self.num_classes);
+        for (i, neighbors) in neighbors.iter().enumerate() {
+            let weights = self.search.weights(neighbors);
+            let total: f64 = weights.iter().sum();
+            for (&(j, _), weight) in neighbors.iter().zip(weights) {
+                votes[(i, self.classes[j])] += weight / total;
+            }
+        }
+        votes
+    }
+
+    /// Predicts the class of each of `records`, with ties going to the class with the lowest
+    /// index.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, records: &[T]) -> Vec<T::Target> {
+        self.predict_proba(records)
+            .rows()
+            .map(|votes| {
+                let best = votes
+                    .iter()
+                    .enumerate()
+                    .fold(0, |best, (k, &v)| if v > votes[best] { k } else { best });
+                T::Target::from_index(best)
+            })
+            .collect()
+    }
+}
+
+/// Predicts the target of samples as the (weighted) mean target of their `k` nearest neighbors
+/// in the training set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KNeighborsRegressor<T> {
+    search: Search,
+    targets: Vec<f64>,
+    records: PhantomData<T>,
+}
+
+impl<T: Dataset> KNeighborsRegressor<T>
+where
+    T::Target: Into<f64>,
+{
+    /// Regressor averaging `num_neighbors` neighbors, with the [Euclidean](Metric::Euclidean)
+    /// metric and [uniform](Weights::Uniform) weights.
+    pub fn new(num_neighbors: usize) -> Self {
+        Self {
+            search: Search::new(num_neighbors),
+            targets: Vec::new(),
+            records: PhantomData,
+        }
+    }
+
+    pub fn with_weights(mut self, weights: Weights) -> Self {
+        self.search.weights = weights;
+        self
+    }
+
+    pub fn with_metric(mut self, metric: Metric) -> Self {
+        self.search.metric = metric;
+        self
+    }
+
+    /// Stores the features and targets of `records` as the training set.
+    pub fn fit(&mut self, records: &[T]) {
+        self.search.fit(T::to_matrix(records));
+        self.targets = records.iter().map(|r| r.target().into()).collect();
+    }
+
+    /// Returns the index in the training set and the distance of the nearest neighbors of each
+    /// of `records`, from nearest to farthest.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn kneighbors(&self, records: &[T]) -> Vec<Vec<(usize, f64)>> {
+        self.search.kneighbors(&T::to_matrix(records))
+    }
+
+    /// Predicts the target of each of `records`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, records: &[T]) -> Vec<f32> {
+        self.kneighbors(records)
+            .iter()
+            .map(|neighbors| {
+                let weights = self.search.weights(neighbors);
+                let total: f64 = weights.iter().sum();
+                let sum: f64 = neighbors
+                    .iter()
+                    .zip(weights)
+                    .map(|(&(j, _), weight)| self.targets[j] * weight)
+                    .sum();
+                (sum / total) as f32
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn classifies_iris_and_digits() {
+    use crate::datasets::{digits, iris};
+    use crate::metrics::classification::accuracy;
+    use crate::model_selection::train_test_split;
+
+    let (train, test) = train_test_split(iris::DATA, 0.3, 0, true);
+    let mut knn = KNeighborsClassifier::new(5);
+    knn.fit(&train);
+    let y_true: Vec<_> = test.iter().map(Dataset::target).collect();
+    assert!(accuracy(&y_true, &knn.predict(&test)) > 0.9);
+
+    let (train, test) = train_test_split(&digits::DATA, 0.25, 0, true);
+    let y_true: Vec<_> = test.iter().map(Dataset::target).collect();
+    for metric in [Metric::Euclidean, Metric::Manhattan, Metric::Cosine] {
+        let mut knn = KNeighborsClassifier::new(3)
+            .with_weights(Weights::Distance)
+            .with_metric(metric);
+        knn.fit(&train);
+        assert!(accuracy(&y_true, &knn.predict(&test)) > 0.95, "{metric:?}");
+    }
+}
+
+#[test]
+fn regression_weights() {
+    use crate::datasets::diabetes;
+
+    let data = &diabetes::DATA[..50];
+    let y: Vec<f32> = data.iter().map(|r| r.target as f32).collect();
+
+    // Every sample is its own nearest neighbor, at distance zero.
+    let mut knn = KNeighborsRegressor::new(3).with_weights(Weights::Distance);
+    knn.fit(data);
+    assert_eq!(knn.predict(data), y);
+
+    let mut knn = KNeighborsRegressor::new(3).with_metric(Metric::Minkowski(3.0));
+    knn.fit(data);
+    let neighbors = knn.kneighbors(&data[..1]);
+    assert_eq!(neighbors[0][0], (0, 0.0));
+    let mean = neighbors[0].iter().map(|&(j, _)| y[j]).sum::<f32>() / 3.0;
+    assert!((knn.predict(&data[..1])[0] - mean).abs() < 1e-3);
+}
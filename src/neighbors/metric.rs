@@ -0,0 +1,59 @@
+/// Distance between two feature rows.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Metric {
+    /// Straight-line distance, the L2 norm of the difference (the default).
+    #[default]
+    Euclidean,
+
+    /// Sum of absolute differences, the L1 norm of the difference.
+    Manhattan,
+
+    /// Lp norm of the difference, for `p >= 1`: Manhattan for 1, Euclidean for 2.
+    Minkowski(f64),
+
+    /// One minus the cosine of the angle between the rows, which ignores their lengths. Rows of
+    /// zeros are at distance 1 from everything.
+    Cosine,
+}
+
+impl Metric {
+    pub fn distance(&self, a: &[f32], b: &[f32]) -> f64 {
+        assert_eq!(a.len(), b.len(), "Invalid row length");
+        let pairs = a.iter().zip(b).map(|(&a, &b)| (a as f64, b as f64));
+        match *self {
+            Metric::Euclidean => pairs.map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt(),
+            Metric::Manhattan => pairs.map(|(a, b)| (a - b).abs()).sum(),
+            Metric::Minkowski(p) => {
+                assert!(p >= 1.0, "Invalid Minkowski order");
+                pairs
+                    .map(|(a, b)| (a - b).abs().powf(p))
+                    .sum::<f64>()
+                    .powf(p.recip())
+            }
+            Metric::Cosine => {
+                let (mut dot, mut norm_a, mut norm_b) = (0.0, 0.0, 0.0);
+                for (a, b) in pairs {
+                    dot += a * b;
+                    norm_a += a * a;
+                    norm_b += b * b;
+                }
+                if norm_a == 0.0 || norm_b == 0.0 {
+                    1.0
+                } else {
+                    1.0 - dot / (norm_a * norm_b).sqrt()
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn distances() {
+    let (a, b) = ([0.0, 3.0], [4.0, 0.0]);
+    assert_eq!(Metric::Euclidean.distance(&a, &b), 5.0);
+    assert_eq!(Metric::Manhattan.distance(&a, &b), 7.0);
+    assert!((Metric::Minkowski(2.0).distance(&a, &b) - 5.0).abs() < 1e-12);
+    assert!((Metric::Minkowski(3.0).distance(&a, &b) - 91f64.cbrt()).abs() < 1e-12);
+    assert_eq!(Metric::Cosine.distance(&a, &b), 1.0);
+    assert!(Metric::Cosine.distance(&[1.0, 1.0], &[2.0, 2.0]).abs() < 1e-12);
+}
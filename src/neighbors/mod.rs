@@ -0,0 +1,7 @@
+mod knn;
+mod metric;
+mod tree;
+
+pub use knn::{KNeighborsClassifier, KNeighborsRegressor, Weights};
+pub use metric::Metric;
+pub use tree::{BallTree, KdTree};
@@ -0,0 +1,378 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use super::Metric;
+use crate::datasets::Dataset;
+use crate::matrix::Matrix;
+
+/// Maximum number of points in a leaf, unless another leaf size is given.
+const DEFAULT_LEAF_SIZE: usize = 40;
+
+/// Region of feature space holding every point of a node, used to skip nodes that cannot hold
+/// neighbors.
+trait Bound {
+    fn new(points: &Matrix, indices: &[usize], metric: Metric) -> Self;
+
+    /// Lower bound on the distance from `point` to any point inside the bound.
+    fn min_distance(&self, point: &[f32], metric: Metric) -> f64;
+}
+
+/// Axis-aligned bounding box of the points of a [KdTree] node.
+#[derive(Clone, Debug, PartialEq)]
+struct BoundingBox {
+    min: Vec<f32>,
+    max: Vec<f32>,
+}
+
+impl Bound for BoundingBox {
+    fn new(points: &Matrix, indices: &[usize], _: Metric) -> Self {
+        let mut min = vec![f32::INFINITY; points.num_cols()];
+        let mut max = vec![f32::NEG_INFINITY; points.num_cols()];
+        for &i in indices {
+            for (j, &x) in points.row(i).iter().enumerate() {
+                min[j] = min[j].min(x);
+                max[j] = max[j].max(x);
+            }
+        }
+        Self { min, max }
+    }
+
+    fn min_distance(&self, point: &[f32], metric: Metric) -> f64 {
+        // Distance to the closest point of the box, which differs from `point` only in the
+        // coordinates that lie outside of the box.
+        let closest: Vec<f32> = point
+            .iter()
+            .zip(self.min.iter().zip(&self.max))
+            .map(|(&x, (&min, &max))| x.clamp(min, max))
+            .collect();
+        metric.distance(point, &closest)
+    }
+}
+
+/// Ball around the centroid of the points of a [BallTree] node.
+#[derive(Clone, Debug, PartialEq)]
+struct Ball {
+    center: Vec<f32>,
+    radius: f64,
+}
+
+impl Bound for Ball {
+    fn new(points: &Matrix, indices: &[usize], metric: Metric) -> Self {
+        let mut center = vec![0.0f64; points.num_cols()];
+        for &i in indices {
+            center
+                .iter_mut()
+                .zip(points.row(i))
+                .for_each(|(c, &x)| *c += x as f64);
+        }
+        let center: Vec<f32> = center
+            .iter()
+            .map(|c| (c / indices.len() as f64) as f32)
+            .collect();
+        let radius = indices
+            .iter()
+            .map(|&i| metric.distance(&center, points.row(i)))
+            .fold(0.0, f64::max);
+        Self { center, radius }
+    }
+
+    fn min_distance(&self, point: &[f32], metric: Metric) -> f64 {
+        // Triangle inequality.
+        (metric.distance(point, &self.center) - self.radius).max(0.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Node<B> {
+    /// Range of the points of the node in [Tree::indices].
+    start: usize,
+    end: usize,
+    bound: B,
+    children: Option<(usize, usize)>,
+}
+
+/// Binary space partitioning tree over the rows of a matrix.
+#[derive(Clone, Debug, PartialEq)]
+struct Tree<B> {
+    points: Matrix,
+    metric: Metric,
+
+    /// Row indices of the points, ordered so that the points of each node are contiguous.
+    indices: Vec<usize>,
+    nodes: Vec<Node<B>>,
+}
+
+/// A neighbor candidate, ordered by distance and then by index.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Candidate(f64, usize);
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+impl<B: Bound> Tree<B> {
+    fn new(points: Matrix, metric: Metric, leaf_size: usize) -> Self {
+        assert!(leaf_size > 0, "Invalid leaf size");
+        if metric == Metric::Cosine {
+            panic!("Cosine distance does not satisfy the triangle inequality");
+        }
+
+        let mut tree = Self {
+            indices: (0..points.num_rows()).collect(),
+            points,
+            metric,
+            nodes: Vec::new(),
+        };
+        tree.build(0, tree.indices.len(), leaf_size);
+        tree
+    }
+
+    /// Adds the node holding `indices[start..end]` and its descendants, and returns its position.
+    fn build(&mut self, start: usize, end: usize, leaf_size: usize) -> usize {
+        let bound = B::new(&self.points, &self.indices[start..end], self.metric);
+        let node = self.nodes.len();
+        self.nodes.push(Node {
+            start,
+            end,
+            bound,
+            children: None,
+        });
+
+        if end - start > leaf_size {
+            // Split at the median of the coordinate with the largest spread.
+            let points = &self.points;
+            let spread = |j: usize| {
+                let values = self.indices[start..end].iter().map(|&i| points[(i, j)]);
+                let (min, max) = values.fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), x| {
+                    (lo.min(x), hi.max(x))
+                });
+                max - min
+            };
+            let axis = (0..points.num_cols())
+                .max_by(|&a, &b| spread(a).total_cmp(&spread(b)))
+                .unwrap_or(0);
+
+            let middle = (end - start) / 2;
+            self.indices[start..end].select_nth_unstable_by(middle, |&a, &b| {
+                points[(a, axis)].total_cmp(&points[(b, axis)])
+            });
+            let left = self.build(start, start + middle, leaf_size);
+            let right = self.build(start + middle, end, leaf_size);
+            self.nodes[node].children = Some((left, right));
+        }
+        node
+    }
+
+    fn query(&self, point: &[f32], k: usize) -> Vec<(usize, f64)> {
+        assert_eq!(point.len(), self.points.num_cols(), "Invalid row length");
+        assert!(
+            k <= self.points.num_rows(),
+            "Cannot have more neighbors than points"
+        );
+
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search_nearest(0, point, k, &mut heap);
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Candidate(distance, i)| (i, distance))
+            .collect()
+    }
+
+    /// Adds the neighbors from `node` that are closer than the farthest of the `k` best ones so
+    /// far in `heap`.
+    fn search_nearest(
+        &self,
+        node: usize,
+        point: &[f32],
+        k: usize,
+        heap: &mut BinaryHeap<Candidate>,
+    ) {
+        let node = &self.nodes[node];
+        match node.children {
+            None => {
+                for &i in &self.indices[node.start..node.end] {
+                    let candidate = Candidate(self.metric.distance(point, self.points.row(i)), i);
+                    if heap.len() < k {
+                        heap.push(candidate);
+                    } else if candidate < *heap.peek().unwrap() {
+                        heap.pop();
+                        heap.push(candidate);
+                    }
+                }
+            }
+            Some((left, right)) => {
+                let mut children = [left, right].map(|child| {
+                    (
+                        self.nodes[child].bound.min_distance(point, self.metric),
+                        child,
+                    )
+                });
+                // The closer child first, since it is more likely to tighten the bound.
+                children.sort_by(|a, b| a.0.total_cmp(&b.0));
+                for (min_distance, child) in children {
+                    if heap.len() < k || min_distance <= heap.peek().unwrap().0 {
+                        self.search_nearest(child, point, k, heap);
+                    }
+                }
+            }
+        }
+    }
+
+    fn query_radius(&self, point: &[f32], radius: f64) -> Vec<(usize, f64)> {
+        assert_eq!(point.len(), self.points.num_cols(), "Invalid row length");
+        if self.indices.is_empty() {
+            // The bound of an empty root is inverted, and cannot be measured against.
+            return Vec::new();
+        }
+
+        let mut found = Vec::new();
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            if node.bound.min_distance(point, self.metric) > radius {
+                continue;
+            }
+            match node.children {
+                Some((left, right)) => stack.extend([left, right]),
+                None => {
+                    for &i in &self.indices[node.start..node.end] {
+                        let distance = self.metric.distance(point, self.points.row(i));
+                        if distance <= radius {
+                            found.push(Candidate(distance, i));
+                        }
+                    }
+                }
+            }
+        }
+        found.sort();
+        found
+            .into_iter()
+            .map(|Candidate(distance, i)| (i, distance))
+            .collect()
+    }
+}
+
+macro_rules! spatial_index {
+    ($(#[$attr:meta])* $name:ident, $bound:ty) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct $name(Tree<$bound>);
+
+        impl $name {
+            /// Indexes the rows of `points`, with leaves of up to 40 points.
+            ///
+            /// Panics for the [Cosine](Metric::Cosine) metric, which is not a true distance.
+            pub fn new(points: Matrix, metric: Metric) -> Self {
+                Self::with_leaf_size(points, metric, DEFAULT_LEAF_SIZE)
+            }
+
+            /// Indexes the rows of `points`, with leaves of up to `leaf_size` points. Smaller
+            /// leaves mean a deeper tree that prunes more, but costs more to traverse.
+            pub fn with_leaf_size(points: Matrix, metric: Metric, leaf_size: usize) -> Self {
+                Self(Tree::new(points, metric, leaf_size))
+            }
+
+            /// Indexes the features of `records`.
+            pub fn from_records<T: Dataset>(records: &[T], metric: Metric) -> Self {
+                Self::new(T::to_matrix(records), metric)
+            }
+
+            /// Returns the row index and the distance of the `k` points nearest to `point`, from
+            /// nearest to farthest. Ties go to the lowest index, as in a brute-force search.
+            pub fn query(&self, point: &[f32], k: usize) -> Vec<(usize, f64)> {
+                self.0.query(point, k)
+            }
+
+            /// Returns the row index and the distance of every point within `radius` of
+            /// `point`, from nearest to farthest.
+            pub fn query_radius(&self, point: &[f32], radius: f64) -> Vec<(usize, f64)> {
+                self.0.query_radius(point, radius)
+            }
+
+            /// The indexed points.
+            pub fn points(&self) -> &Matrix {
+                &self.0.points
+            }
+
+            pub fn metric(&self) -> Metric {
+                self.0.metric
+            }
+        }
+    };
+}
+
+spatial_index!(
+    /// Spatial index that recursively splits the points with axis-aligned planes. Fast for a
+    /// moderate number of features.
+    KdTree,
+    BoundingBox
+);
+
+spatial_index!(
+    /// Spatial index that recursively groups the points into nested balls. Degrades more
+    /// gracefully than a [KdTree] as the number of features grows.
+    BallTree,
+    Ball
+);
+
+#[test]
+fn queries_match_brute_force() {
+    use crate::datasets::digits;
+
+    let points = digits::Digits::to_matrix(&digits::DATA);
+    let brute_force = |metric: Metric, query: &[f32]| {
+        let mut all: Vec<Candidate> = points
+            .rows()
+            .enumerate()
+            .map(|(i, row)| Candidate(metric.distance(query, row), i))
+            .collect();
+        all.sort();
+        all.into_iter()
+            .map(|Candidate(d, i)| (i, d))
+            .collect::<Vec<_>>()
+    };
+
+    let metrics = [Metric::Euclidean, Metric::Manhattan, Metric::Minkowski(3.0)];
+    for metric in metrics {
+        let kd_tree = KdTree::from_records(&digits::DATA, metric);
+        let ball_tree = BallTree::with_leaf_size(points.clone(), metric, 10);
+        for i in (0..points.num_rows()).step_by(97) {
+            let query = points.row(i);
+            let expected = brute_force(metric, query);
+            assert_eq!(kd_tree.query(query, 7), expected[..7], "{metric:?}");
+            assert_eq!(ball_tree.query(query, 7), expected[..7], "{metric:?}");
+
+            // Radius of the 20th neighbor, so that the query finds at least 20 points.
+            let radius = expected[19].1;
+            let within: Vec<_> = expected
+                .iter()
+                .take_while(|n| n.1 <= radius)
+                .copied()
+                .collect();
+            assert_eq!(kd_tree.query_radius(query, radius), within, "{metric:?}");
+            assert_eq!(ball_tree.query_radius(query, radius), within, "{metric:?}");
+        }
+    }
+}
+
+#[test]
+fn empty_trees() {
+    let points = Matrix::new(0, 2, Vec::new());
+    let kd_tree = KdTree::new(points.clone(), Metric::Euclidean);
+    let ball_tree = BallTree::new(points, Metric::Euclidean);
+    assert_eq!(kd_tree.query_radius(&[0.0, 0.0], 1.0), []);
+    assert_eq!(ball_tree.query_radius(&[0.0, 0.0], 1.0), []);
+    assert_eq!(kd_tree.query(&[0.0, 0.0], 0), []);
+}
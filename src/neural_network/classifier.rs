@@ -0,0 +1,146 @@
+use std::marker::PhantomData;
+
+use super::network::{MlpFit, Network, Output};
+use super::{mlp_params_builders, MlpParams};
+use crate::datasets::ClassLabel;
+use crate::matrix::Matrix;
+
+/// Multilayer perceptron classifier, trained by backpropagation of the cross-entropy.
+///
+/// Two classes are modeled with a single logistic output unit, more classes with softmax
+/// units.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MLPClassifier<L> {
+    params: MlpParams,
+    fitted: Option<MlpFit>,
+    labels: PhantomData<L>,
+}
+
+/// Probability of each class for each row of the outputs of the network.
+fn class_probabilities(outputs: Matrix<f64>) -> Matrix<f64> {
+    if outputs.num_cols() > 1 {
+        return outputs;
+    }
+    let rows: Vec<[f64; 2]> = outputs.as_slice().iter().map(|&p| [1.0 - p, p]).collect();
+    Matrix::from_rows(&rows)
+}
+
+fn most_probable(probabilities: &[f64]) -> usize {
+    (0..probabilities.len())
+        .rev()
+        .max_by(|&a, &b| probabilities[a].total_cmp(&probabilities[b]))
+        .unwrap()
+}
+
+impl<L: ClassLabel> MLPClassifier<L> {
+    /// One hidden layer of 100 ReLU units, trained with Adam.
+    pub fn new() -> Self {
+        Self {
+            params: MlpParams::default(),
+            fitted: None,
+            labels: PhantomData,
+        }
+    }
+
+    mlp_params_builders!();
+
+    /// Trains the network on the rows of `x` and their labels `y`.
+    ///
+    /// The classes are the labels up to the largest [ClassLabel::index] in `y`. When stopping
+    /// early, the validation score is the accuracy.
+    pub fn fit(&mut self, x: &Matrix, y: &[L]) {
+        let num_classes = y.iter().map(|label| label.index() + 1).max().unwrap_or(0);
+        let (output, encoded) = if num_classes <= 2 {
+            let targets: Vec<f64> = y.iter().map(|label| label.index() as f64).collect();
+            (Output::Logistic, Matrix::from_column(&targets))
+        } else {
+            let mut one_hot = Matrix::zeros(y.len(), num_classes);
+            for (i, label) in y.iter().enumerate() {
+                one_hot[(i, label.index())] = 1.0;
+            }
+            (Output::Softmax, one_hot)
+        };
+
+        let accuracy = |network: &Network, x: &Matrix, y: &Matrix<f64>| {
+            let probabilities = class_probabilities(network.predict(x));
+            let correct = probabilities
+                .rows()
+                .zip(class_probabilities(y.clone()).rows())
+                .filter(|(p, y)| most_probable(p) == most_probable(y))
+                .count();
+            correct as f64 / x.num_rows() as f64
+        };
+        self.fitted = Some(MlpFit::train(x, &encoded, output, &self.params, accuracy));
+    }
+
+    /// Returns the probability of each class (columns, in [ClassLabel::index] order) for each
+    /// row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict_proba(&self, x: &Matrix) -> Matrix<f64> {
+        let fitted = self.fitted.as_ref().expect("Model not fitted");
+        class_probabilities(fitted.network.predict(x))
+    }
+
+    /// Predicts the most probable class of each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<L> {
+        self.predict_proba(x)
+            .rows()
+            .map(|row| L::from_index(most_probable(row)))
+            .collect()
+    }
+}
+
+impl<L: ClassLabel> Default for MLPClassifier<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn digits_and_breast_cancer() {
+    use super::{Activation, Optimizer};
+    use crate::datasets::breast_cancer::{self, BreastCancer};
+    use crate::datasets::digits::{self, Digits};
+    use crate::datasets::Dataset;
+    use crate::metrics::classification::accuracy;
+    use crate::model_selection::train_test_split;
+    use crate::utils::scale::{Scaler, StandardScaler};
+
+    // Pixel intensities range from 0 to 16.
+    let (train, test) = train_test_split(&digits::DATA, 0.25, 0, true);
+    let (x_train, y_train) = Digits::to_xy(&train);
+    let (x_test, y_test) = Digits::to_xy(&test);
+    let (x_train, x_test) = (x_train.map(|v| v / 16.0), x_test.map(|v| v / 16.0));
+
+    let mut model = MLPClassifier::new()
+        .with_hidden_layers(&[32])
+        .with_batch_size(64)
+        .with_learning_rate(0.01)
+        .with_max_iter(30);
+    model.fit(&x_train, &y_train);
+    assert!(accuracy(&y_test, &model.predict(&x_test)) > 0.95);
+    let losses = model.loss_curve().unwrap();
+    assert!(losses.last().unwrap() < &(losses[0] / 5.0));
+    assert_eq!(model.coefs().unwrap()[1].shape(), (32, 10));
+
+    let (train, test) = train_test_split(&breast_cancer::DATA, 0.25, 0, true);
+    let (x_train, y_train) = BreastCancer::to_xy(&train);
+    let (x_test, y_test) = BreastCancer::to_xy(&test);
+    let mut scaler = StandardScaler::new();
+    scaler.fit(&x_train.rows().collect::<Vec<_>>());
+    let scale = |x: &Matrix| Matrix::from_rows(&scaler.transform(&x.rows().collect::<Vec<_>>()));
+
+    let mut model = MLPClassifier::new()
+        .with_hidden_layers(&[16, 8])
+        .with_activation(Activation::Tanh)
+        .with_optimizer(Optimizer::Sgd { momentum: 0.9 })
+        .with_learning_rate(0.01)
+        .with_batch_size(32)
+        .with_early_stopping(0.1);
+    model.fit(&scale(&x_train), &y_train);
+    assert!(accuracy(&y_test, &model.predict(&scale(&x_test))) > 0.95);
+    assert!(model.num_iter().unwrap() < 200);
+}
@@ -0,0 +1,205 @@
+mod classifier;
+mod network;
+mod optimizer;
+mod regressor;
+
+pub use classifier::MLPClassifier;
+pub use regressor::MLPRegressor;
+
+/// Activation function of the hidden layers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Activation {
+    /// `max(0, x)` (the default).
+    #[default]
+    Relu,
+    Tanh,
+
+    /// `1 / (1 + e^-x)`.
+    Logistic,
+}
+
+impl Activation {
+    fn apply(&self, x: f64) -> f64 {
+        match self {
+            Activation::Relu => x.max(0.0),
+            Activation::Tanh => x.tanh(),
+            Activation::Logistic => 1.0 / (1.0 + (-x).exp()),
+        }
+    }
+
+    /// Derivative of the activation, in terms of its output `y`.
+    fn derivative(&self, y: f64) -> f64 {
+        match self {
+            Activation::Relu => {
+                if y > 0.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Activation::Tanh => 1.0 - y * y,
+            Activation::Logistic => y * (1.0 - y),
+        }
+    }
+}
+
+/// Gradient-based optimizer of the weights.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Optimizer {
+    /// Stochastic gradient descent, with the given momentum (e.g. 0.9, or 0 for none).
+    Sgd { momentum: f64 },
+
+    /// Adam, with the usual decay rates 0.9 and 0.999 (the default).
+    #[default]
+    Adam,
+}
+
+/// Early stopping on the score of a held-out validation set.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct EarlyStopping {
+    validation_fraction: f32,
+}
+
+/// Settings shared by [MLPClassifier] and [MLPRegressor].
+#[derive(Clone, Debug, PartialEq)]
+struct MlpParams {
+    hidden_layers: Vec<usize>,
+    activation: Activation,
+    optimizer: Optimizer,
+    learning_rate: f64,
+    batch_size: usize,
+    max_iter: usize,
+    l2_regularization: f64,
+    tol: f64,
+    patience: usize,
+    early_stopping: Option<EarlyStopping>,
+    seed: u64,
+}
+
+impl Default for MlpParams {
+    fn default() -> Self {
+        Self {
+            hidden_layers: vec![100],
+            activation: Activation::Relu,
+            optimizer: Optimizer::Adam,
+            learning_rate: 1e-3,
+            batch_size: 200,
+            max_iter: 200,
+            l2_regularization: 1e-4,
+            tol: 1e-4,
+            patience: 10,
+            early_stopping: None,
+            seed: 0,
+        }
+    }
+}
+
+/// Builder methods for the [MlpParams] in the `params` field of an estimator.
+macro_rules! mlp_params_builders {
+    () => {
+        /// Number of units of each hidden layer (a single layer of 100 by default).
+        pub fn with_hidden_layers(mut self, hidden_layers: &[usize]) -> Self {
+            assert!(hidden_layers.iter().all(|&n| n > 0), "Invalid layer size");
+            self.params.hidden_layers = hidden_layers.to_vec();
+            self
+        }
+
+        /// Activation of the hidden layers ([ReLU](super::Activation::Relu) by default).
+        pub fn with_activation(mut self, activation: super::Activation) -> Self {
+            self.params.activation = activation;
+            self
+        }
+
+        /// Optimizer of the weights ([Adam](super::Optimizer::Adam) by default).
+        pub fn with_optimizer(mut self, optimizer: super::Optimizer) -> Self {
+            self.params.optimizer = optimizer;
+            self
+        }
+
+        /// Step size of the optimizer (1e-3 by default).
+        pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+            assert!(learning_rate > 0.0, "Invalid learning rate");
+            self.params.learning_rate = learning_rate;
+            self
+        }
+
+        /// Number of samples per gradient step (200 by default, or all samples if fewer).
+        pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+            assert!(batch_size > 0, "Invalid batch size");
+            self.params.batch_size = batch_size;
+            self
+        }
+
+        /// Maximum number of passes over the training set (200 by default).
+        pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+            self.params.max_iter = max_iter;
+            self
+        }
+
+        /// L2 penalty on the weights (1e-4 by default).
+        pub fn with_l2_regularization(mut self, l2_regularization: f64) -> Self {
+            self.params.l2_regularization = l2_regularization;
+            self
+        }
+
+        /// Training stops once the loss (or the validation score, when stopping early) has not
+        /// improved by `tol` for `patience` passes (1e-4 and 10 by default).
+        pub fn with_tol(mut self, tol: f64, patience: usize) -> Self {
+            assert!(patience > 0, "Invalid patience");
+            self.params.tol = tol;
+            self.params.patience = patience;
+            self
+        }
+
+        /// Holds out `validation_fraction` of the training samples, stops once the score on
+        /// them no longer improves, and keeps the weights with the best score.
+        pub fn with_early_stopping(mut self, validation_fraction: f32) -> Self {
+            assert!(
+                validation_fraction > 0.0 && validation_fraction < 1.0,
+                "Invalid validation fraction"
+            );
+            self.params.early_stopping = Some(super::EarlyStopping {
+                validation_fraction,
+            });
+            self
+        }
+
+        /// Seed of the initial weights, of the mini-batches and of the validation set.
+        pub fn with_seed(mut self, seed: u64) -> Self {
+            self.params.seed = seed;
+            self
+        }
+
+        /// Number of passes over the training set, once fitted.
+        pub fn num_iter(&self) -> Option<usize> {
+            self.fitted.as_ref().map(|fitted| fitted.loss_curve.len())
+        }
+
+        /// Mean training loss of each pass, once fitted.
+        pub fn loss_curve(&self) -> Option<&[f64]> {
+            self.fitted.as_ref().map(|fitted| &fitted.loss_curve[..])
+        }
+
+        /// Validation score after each pass, once fitted with early stopping.
+        pub fn validation_scores(&self) -> Option<&[f64]> {
+            self.fitted.as_ref()?.validation_scores.as_deref()
+        }
+
+        /// Weights of each layer, with one row per input and one column per unit, once
+        /// fitted.
+        pub fn coefs(&self) -> Option<&[Matrix<f64>]> {
+            self.fitted
+                .as_ref()
+                .map(|fitted| &fitted.network.weights[..])
+        }
+
+        /// Biases of the units of each layer, once fitted.
+        pub fn intercepts(&self) -> Option<&[Vec<f64>]> {
+            self.fitted
+                .as_ref()
+                .map(|fitted| &fitted.network.biases[..])
+        }
+    };
+}
+
+use mlp_params_builders;
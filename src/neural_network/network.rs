@@ -0,0 +1,276 @@
+use super::optimizer::OptimizerState;
+use super::{Activation, MlpParams};
+use crate::matrix::Matrix;
+use crate::utils::random::Rng;
+
+/// Output units and the loss they are trained with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Output {
+    /// Linear units with half the squared error.
+    Identity,
+
+    /// A single logistic unit with the binary cross-entropy.
+    Logistic,
+
+    /// Softmax units with the cross-entropy.
+    Softmax,
+}
+
+/// A fully connected feed-forward network.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Network {
+    /// Weights of each layer, with one row per input and one column per unit.
+    pub(super) weights: Vec<Matrix<f64>>,
+    pub(super) biases: Vec<Vec<f64>>,
+    activation: Activation,
+    output: Output,
+}
+
+impl Network {
+    /// Network with layers of the given `sizes`, inputs first, and Glorot-initialized weights.
+    fn new(sizes: &[usize], activation: Activation, output: Output, rng: &mut Rng) -> Self {
+        let (weights, biases) = sizes
+            .windows(2)
+            .map(|pair| {
+                let (inputs, units) = (pair[0], pair[1]);
+                let gain = if activation == Activation::Logistic {
+                    2.0
+                } else {
+                    6.0
+                };
+                let bound = (gain / (inputs + units) as f64).sqrt();
+                let mut uniform = || rng.uniform(-bound, bound);
+                let weights = (0..inputs * units).map(|_| uniform()).collect();
+                let biases = (0..units).map(|_| uniform()).collect();
+                (Matrix::new(inputs, units, weights), biases)
+            })
+            .unzip();
+        Self {
+            weights,
+            biases,
+            activation,
+            output,
+        }
+    }
+
+    /// Outputs of every layer for the rows of `x`, starting with `x` itself.
+    fn forward(&self, x: Matrix<f64>) -> Vec<Matrix<f64>> {
+        let mut outputs = vec![x];
+        for (l, (weights, biases)) in self.weights.iter().zip(&self.biases).enumerate() {
+            let mut z = outputs[l].matmul(weights).add_row(biases);
+            if l + 1 < self.weights.len() {
+                z = z.map(|v| self.activation.apply(v));
+            } else {
+                match self.output {
+                    Output::Identity => {}
+                    Output::Logistic => z = z.map(|v| 1.0 / (1.0 + (-v).exp())),
+                    Output::Softmax => {
+                        for i in 0..z.num_rows() {
+                            let row = z.row_mut(i);
+                            let max = row.iter().fold(f64::NEG_INFINITY, |max, &v| max.max(v));
+                            row.iter_mut().for_each(|v| *v = (*v - max).exp());
+                            let sum: f64 = row.iter().sum();
+                            row.iter_mut().for_each(|v| *v /= sum);
+                        }
+                    }
+                }
+            }
+            outputs.push(z);
+        }
+        outputs
+    }
+
+    /// Outputs of the network for the rows of `x`.
+    pub(super) fn predict(&self, x: &Matrix) -> Matrix<f64> {
+        assert_eq!(
+            x.num_cols(),
+            self.weights[0].num_rows(),
+            "Invalid number of columns"
+        );
+        self.forward(x.map(f64::from)).pop().unwrap()
+    }
+
+    /// Mean loss of the outputs for the rows of `x`, and its gradients with respect to the
+    /// weights and biases of each layer.
+    fn gradients(
+        &self,
+        x: Matrix<f64>,
+        y: &Matrix<f64>,
+        l2: f64,
+    ) -> (f64, Vec<Matrix<f64>>, Vec<Vec<f64>>) {
+        let n = x.num_rows() as f64;
+        let outputs = self.forward(x);
+        let predicted = outputs.last().unwrap();
+
+        let clamp = |p: f64| p.clamp(1e-15, 1.0 - 1e-15);
+        let pairs = predicted.as_slice().iter().zip(y.as_slice());
+        let loss: f64 = match self.output {
+            Output::Identity => pairs.map(|(p, y)| (p - y).powi(2) / 2.0).sum(),
+            Output::Logistic => pairs
+                .map(|(&p, y)| -(y * clamp(p).ln() + (1.0 - y) * (1.0 - clamp(p)).ln()))
+                .sum(),
+            Output::Softmax => pairs.map(|(&p, y)| -y * clamp(p).ln()).sum(),
+        };
+        let penalty: f64 = self
+            .weights
+            .iter()
+            .flat_map(|w| w.as_slice())
+            .map(|w| w * w)
+            .sum();
+
+        // Every output loss is paired with the activation that makes this its gradient with
+        // respect to the weighted inputs of the output units.
+        let mut delta = predicted.zip_map(y, |p, y| (p - y) / n);
+        let mut weight_gradients = Vec::with_capacity(self.weights.len());
+        let mut bias_gradients = Vec::with_capacity(self.weights.len());
+        for l in (0..self.weights.len()).rev() {
+            let gradient = outputs[l].transpose().matmul(&delta);
+            weight_gradients.push(gradient.zip_map(&self.weights[l], |g, w| g + l2 * w / n));
+            bias_gradients.push(delta.col_sums());
+            if l > 0 {
+                delta = delta
+                    .matmul(&self.weights[l].transpose())
+                    .zip_map(&outputs[l], |d, a| d * self.activation.derivative(a));
+            }
+        }
+        weight_gradients.reverse();
+        bias_gradients.reverse();
+        (
+            loss / n + l2 * penalty / (2.0 * n),
+            weight_gradients,
+            bias_gradients,
+        )
+    }
+}
+
+/// A network trained on a dataset, with the history of its training.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct MlpFit {
+    pub(super) network: Network,
+    pub(super) loss_curve: Vec<f64>,
+    pub(super) validation_scores: Option<Vec<f64>>,
+}
+
+impl MlpFit {
+    /// Trains a network on the rows of `x` and their encoded targets `y` with mini-batches.
+    ///
+    /// When stopping early, `score` (higher is better) rates the network on the validation
+    /// rows and targets.
+    pub(super) fn train(
+        x: &Matrix,
+        y: &Matrix<f64>,
+        output: Output,
+        params: &MlpParams,
+        score: impl Fn(&Network, &Matrix, &Matrix<f64>) -> f64,
+    ) -> Self {
+        assert_eq!(x.num_rows(), y.num_rows(), "Inconsistent number of samples");
+        assert!(x.num_rows() > 0, "No samples");
+        let mut rng = Rng::new(params.seed);
+
+        let mut indices: Vec<usize> = (0..x.num_rows()).collect();
+        let validation = params.early_stopping.map(|stopping| {
+            let n = indices.len();
+            let num_validation = ((stopping.validation_fraction * n as f32).ceil() as usize)
+                .clamp(1, n.saturating_sub(1).max(1));
+            rng.shuffle(&mut indices);
+            let validation: Vec<usize> = indices.drain(..num_validation).collect();
+            indices.sort();
+            (x.select_rows(&validation), y.select_rows(&validation))
+        });
+
+        let sizes: Vec<usize> = [x.num_cols()]
+            .into_iter()
+            .chain(params.hidden_layers.iter().copied())
+            .chain([y.num_cols()])
+            .collect();
+        let mut network = Network::new(&sizes, params.activation, output, &mut rng);
+        let mut optimizer = OptimizerState::new(params.optimizer, params.learning_rate);
+
+        let mut fit = Self {
+            network: network.clone(),
+            loss_curve: Vec::new(),
+            validation_scores: validation.as_ref().map(|_| Vec::new()),
+        };
+        let mut best = f64::NEG_INFINITY;
+        let mut num_stale = 0;
+        for _ in 0..params.max_iter {
+            rng.shuffle(&mut indices);
+            let mut total_loss = 0.0;
+            for batch in indices.chunks(params.batch_size) {
+                let (loss, weight_gradients, bias_gradients) = network.gradients(
+                    x.select_rows(batch).map(f64::from),
+                    &y.select_rows(batch),
+                    params.l2_regularization,
+                );
+                total_loss += loss * batch.len() as f64;
+
+                let parameters = network.weights.iter_mut().map(Matrix::as_mut_slice);
+                let parameters = parameters.chain(network.biases.iter_mut().map(|b| &mut b[..]));
+                let gradients = weight_gradients.iter().map(Matrix::as_slice);
+                let gradients = gradients.chain(bias_gradients.iter().map(|b| &b[..]));
+                optimizer.step(parameters.collect(), gradients.collect());
+            }
+            let loss = total_loss / indices.len() as f64;
+            fit.loss_curve.push(loss);
+
+            // Progress is a higher validation score, or a lower training loss.
+            let progress = match &validation {
+                Some((x, y)) => {
+                    let score = score(&network, x, y);
+                    fit.validation_scores.as_mut().unwrap().push(score);
+                    score
+                }
+                None => -loss,
+            };
+            if progress > best + params.tol {
+                num_stale = 0;
+            } else {
+                num_stale += 1;
+            }
+            if progress > best {
+                best = progress;
+                if validation.is_some() {
+                    fit.network = network.clone();
+                }
+            }
+            if num_stale >= params.patience {
+                break;
+            }
+        }
+        if validation.is_none() {
+            fit.network = network;
+        }
+        fit
+    }
+}
+
+#[test]
+fn gradients_match_finite_differences() {
+    let mut rng = Rng::new(0);
+    let x = Matrix::new(3, 2, vec![0.5, -1.0, 1.5, 0.2, -0.3, 0.8]);
+    let targets = [
+        (Output::Identity, vec![0.3, -1.2, 2.0]),
+        (Output::Logistic, vec![1.0, 0.0, 1.0]),
+        (Output::Softmax, vec![1.0, 0.0, 0.0, 1.0, 0.0, 1.0]),
+    ];
+    for (output, y) in targets {
+        let y = Matrix::new(3, y.len() / 3, y);
+        for activation in [Activation::Relu, Activation::Tanh, Activation::Logistic] {
+            let network = Network::new(&[2, 4, y.num_cols()], activation, output, &mut rng);
+            let (_, weight_gradients, bias_gradients) = network.gradients(x.clone(), &y, 0.1);
+
+            let loss = |network: &Network| network.gradients(x.clone(), &y, 0.1).0;
+            for l in 0..2 {
+                let mut shifted = network.clone();
+                shifted.weights[l].as_mut_slice()[1] += 1e-6;
+                let estimate = (loss(&shifted) - loss(&network)) / 1e-6;
+                assert!((estimate - weight_gradients[l].as_slice()[1]).abs() < 1e-4);
+
+                let mut shifted = network.clone();
+                shifted.biases[l][0] += 1e-6;
+                let estimate = (loss(&shifted) - loss(&network)) / 1e-6;
+                assert!((estimate - bias_gradients[l][0]).abs() < 1e-4);
+            }
+        }
+    }
+}
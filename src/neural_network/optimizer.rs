@@ -0,0 +1,62 @@
+use super::Optimizer;
+
+/// Decay rates of the moment estimates of Adam, and the term that keeps its steps finite.
+const BETA1: f64 = 0.9;
+const BETA2: f64 = 0.999;
+const EPSILON: f64 = 1e-8;
+
+/// An [Optimizer] with its per-parameter state: the velocity of SGD, or the first and second
+/// moment estimates of Adam.
+pub(super) struct OptimizerState {
+    optimizer: Optimizer,
+    learning_rate: f64,
+    num_steps: i32,
+    first: Vec<Vec<f64>>,
+    second: Vec<Vec<f64>>,
+}
+
+impl OptimizerState {
+    pub(super) fn new(optimizer: Optimizer, learning_rate: f64) -> Self {
+        Self {
+            optimizer,
+            learning_rate,
+            num_steps: 0,
+            first: Vec::new(),
+            second: Vec::new(),
+        }
+    }
+
+    /// Moves each group of `parameters` against its `gradients`.
+    pub(super) fn step(&mut self, parameters: Vec<&mut [f64]>, gradients: Vec<&[f64]>) {
+        if self.first.is_empty() {
+            self.first = gradients.iter().map(|g| vec![0.0; g.len()]).collect();
+            self.second = self.first.clone();
+        }
+        self.num_steps += 1;
+
+        let groups = parameters.into_iter().zip(gradients);
+        match self.optimizer {
+            Optimizer::Sgd { momentum } => {
+                for ((parameters, gradients), velocity) in groups.zip(&mut self.first) {
+                    for ((p, g), v) in parameters.iter_mut().zip(gradients).zip(velocity) {
+                        *v = momentum * *v - self.learning_rate * g;
+                        *p += *v;
+                    }
+                }
+            }
+            Optimizer::Adam => {
+                // Bias correction of the moment estimates, which start at zero.
+                let rate = self.learning_rate * (1.0 - BETA2.powi(self.num_steps)).sqrt()
+                    / (1.0 - BETA1.powi(self.num_steps));
+                let moments = self.first.iter_mut().zip(&mut self.second);
+                for ((parameters, gradients), (first, second)) in groups.zip(moments) {
+                    for (k, (p, g)) in parameters.iter_mut().zip(gradients).enumerate() {
+                        first[k] = BETA1 * first[k] + (1.0 - BETA1) * g;
+                        second[k] = BETA2 * second[k] + (1.0 - BETA2) * g * g;
+                        *p -= rate * first[k] / (second[k].sqrt() + EPSILON);
+                    }
+                }
+            }
+        }
+    }
+}
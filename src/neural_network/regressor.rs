@@ -0,0 +1,82 @@
+use super::network::{MlpFit, Network, Output};
+use super::{mlp_params_builders, MlpParams};
+use crate::matrix::Matrix;
+use crate::metrics::regression::r2_score;
+
+/// Multilayer perceptron regressor, trained by backpropagation of the squared error, with a
+/// linear output unit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MLPRegressor {
+    params: MlpParams,
+    fitted: Option<MlpFit>,
+}
+
+impl MLPRegressor {
+    /// One hidden layer of 100 ReLU units, trained with Adam.
+    pub fn new() -> Self {
+        Self {
+            params: MlpParams::default(),
+            fitted: None,
+        }
+    }
+
+    mlp_params_builders!();
+
+    /// Trains the network on the rows of `x` and their targets `y`.
+    ///
+    /// When stopping early, the validation score is the R².
+    pub fn fit<Y: Copy + Into<f64>>(&mut self, x: &Matrix, y: &[Y]) {
+        let targets: Vec<f64> = y.iter().map(|&v| v.into()).collect();
+        let r2 = |network: &Network, x: &Matrix, y: &Matrix<f64>| {
+            r2_score(y.as_slice(), network.predict(x).as_slice())
+        };
+        self.fitted = Some(MlpFit::train(
+            x,
+            &Matrix::from_column(&targets),
+            Output::Identity,
+            &self.params,
+            r2,
+        ));
+    }
+
+    /// Predicts the target of each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<f32> {
+        let fitted = self.fitted.as_ref().expect("Model not fitted");
+        let outputs = fitted.network.predict(x);
+        outputs.as_slice().iter().map(|&v| v as f32).collect()
+    }
+}
+
+impl Default for MLPRegressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn sine() {
+    use super::Activation;
+
+    let inputs: Vec<f32> = (0..200).map(|i| i as f32 / 200.0 * 6.0 - 3.0).collect();
+    let x = Matrix::from_column(&inputs);
+    let y: Vec<f32> = inputs.iter().map(|v| v.sin()).collect();
+
+    let mut model = MLPRegressor::new()
+        .with_hidden_layers(&[20, 20])
+        .with_activation(Activation::Tanh)
+        .with_learning_rate(0.01)
+        .with_batch_size(20)
+        .with_max_iter(300);
+    model.fit(&x, &y);
+    assert!(r2_score(&y, &model.predict(&x)) > 0.98);
+
+    let mut stopped = MLPRegressor::new()
+        .with_hidden_layers(&[20])
+        .with_learning_rate(0.01)
+        .with_early_stopping(0.2);
+    stopped.fit(&x, &y);
+    let scores = stopped.validation_scores().unwrap();
+    assert_eq!(scores.len(), stopped.num_iter().unwrap());
+}
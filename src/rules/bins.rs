@@ -0,0 +1,100 @@
+use super::export::format_threshold;
+
+/// Ranges of a continuous target, so that a [RuleListClassifier](super::RuleListClassifier)
+/// can learn readable rules for a regression dataset such as `diabetes::DATA`: the binned
+/// target is a `u8` label, and [TargetBins::names] stands in for the `TARGET_NAMES`.
+///
+/// Bin `k` holds the values above `thresholds[k - 1]` and at most `thresholds[k]`, with the
+/// first and last bins open-ended.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetBins {
+    thresholds: Vec<f64>,
+}
+
+impl TargetBins {
+    /// Bins split at the increasing `thresholds`, one more bin than thresholds.
+    pub fn new(thresholds: Vec<f64>) -> Self {
+        assert!(
+            thresholds.windows(2).all(|pair| pair[0] < pair[1]),
+            "Thresholds must be increasing"
+        );
+        assert!(thresholds.len() < u8::MAX as usize, "Too many bins");
+        Self { thresholds }
+    }
+
+    /// Up to `num_bins` bins with about as many values of `y` in each. Repeated values can
+    /// merge bins.
+    pub fn quantiles<Y: Copy + Into<f64>>(y: &[Y], num_bins: usize) -> Self {
+        assert!(num_bins > 0, "Invalid number of bins");
+        assert!(!y.is_empty(), "No samples");
+        let mut sorted: Vec<f64> = y.iter().map(|&v| v.into()).collect();
+        sorted.sort_by(f64::total_cmp);
+
+        let mut thresholds: Vec<f64> = (1..num_bins)
+            .map(|k| sorted[k * sorted.len() / num_bins - 1])
+            .filter(|&threshold| threshold < sorted[sorted.len() - 1])
+            .collect();
+        thresholds.dedup();
+        Self::new(thresholds)
+    }
+
+    pub fn thresholds(&self) -> &[f64] {
+        &self.thresholds
+    }
+
+    pub fn num_bins(&self) -> usize {
+        self.thresholds.len() + 1
+    }
+
+    /// Bin of each value of `y`, as the label to fit a classifier on.
+    pub fn bin<Y: Copy + Into<f64>>(&self, y: &[Y]) -> Vec<u8> {
+        y.iter()
+            .map(|&v| self.thresholds.partition_point(|&t| t < v.into()) as u8)
+            .collect()
+    }
+
+    /// Name of each bin, phrased with `target_name` (e.g. the `TARGET_NAMES` of the dataset),
+    /// such as `target <= 96` or `96 < target <= 178`.
+    pub fn names(&self, target_name: &str) -> Vec<String> {
+        let format = |value: f64| format_threshold(value as f32);
+        (0..self.num_bins())
+            .map(|k| {
+                let below = k.checked_sub(1).map(|k| self.thresholds[k]);
+                match (below, self.thresholds.get(k)) {
+                    (None, None) => target_name.to_string(),
+                    (None, Some(&above)) => format!("{target_name} <= {}", format(above)),
+                    (Some(below), None) => format!("{target_name} > {}", format(below)),
+                    (Some(below), Some(&above)) => {
+                        format!("{} < {target_name} <= {}", format(below), format(above))
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn diabetes_rules() {
+    use super::RuleListClassifier;
+    use crate::datasets::diabetes::{self, Diabetes};
+    use crate::datasets::Dataset;
+    use crate::metrics::classification::accuracy;
+
+    let (x, y) = Diabetes::to_xy(&diabetes::DATA);
+    let bins = TargetBins::quantiles(&y, 3);
+    assert_eq!(bins.bin(&[25, 300]), [0, 2]);
+    let labels = bins.bin(&y);
+    for k in 0..3 {
+        let count = labels.iter().filter(|&&label| label == k).count();
+        assert!((140..=155).contains(&count));
+    }
+
+    let mut model = RuleListClassifier::<u8>::new().with_min_coverage(10);
+    model.fit(&x, &labels);
+    assert!(accuracy(&labels, &model.predict(&x)) > 0.6);
+
+    let names = bins.names(Diabetes::TARGET_NAMES[0]);
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    let text = model.to_text(&Diabetes::FEATURE_NAMES, &names);
+    assert_eq!(text, include_str!("golden/diabetes.txt"));
+}
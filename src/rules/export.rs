@@ -0,0 +1,180 @@
+use super::{Comparison, Condition, Rule, RuleListClassifier};
+use crate::datasets::ClassLabel;
+
+/// Rounds `value` to three significant digits for display, e.g. 16.795 to `16.8`.
+pub(super) fn format_threshold(value: f32) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return value.to_string();
+    }
+    let decimals = (2 - value.abs().log10().floor() as i32).max(0) as usize;
+    let text = format!("{value:.decimals$}");
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
+
+fn format_condition(condition: &Condition, feature_names: &[&str]) -> String {
+    let operator = match condition.comparison {
+        Comparison::AtMost => "<=",
+        Comparison::Above => ">",
+    };
+    format!(
+        "{} {operator} {}",
+        feature_names[condition.feature],
+        format_threshold(condition.threshold)
+    )
+}
+
+fn format_conditions<L>(rule: &Rule<L>, feature_names: &[&str]) -> String {
+    if rule.conditions.is_empty() {
+        return "otherwise".to_string();
+    }
+    rule.conditions
+        .iter()
+        .map(|condition| format_condition(condition, feature_names))
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+impl<L: ClassLabel> RuleListClassifier<L> {
+    /// Returns the rules and checks that there is a name for every feature and class they use.
+    fn named_rules(&self, feature_names: &[&str], target_names: &[&str]) -> &[Rule<L>] {
+        let rules = self.rules().expect("Model not fitted");
+        for rule in rules {
+            assert!(
+                rule.prediction.index() < target_names.len(),
+                "Missing target names"
+            );
+            assert!(
+                rule.conditions
+                    .iter()
+                    .all(|condition| condition.feature < feature_names.len()),
+                "Missing feature names"
+            );
+        }
+        rules
+    }
+
+    /// Numbered rules, one per line, phrased with `feature_names` and `target_names` (typically
+    /// the `FEATURE_NAMES` and `TARGET_NAMES` of the dataset), along with the number of training
+    /// samples each rule gets right out of those it covers.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn to_text(&self, feature_names: &[&str], target_names: &[&str]) -> String {
+        self.named_rules(feature_names, target_names)
+            .iter()
+            .enumerate()
+            .map(|(k, rule)| {
+                format!(
+                    "{}. {} => {} ({}/{})\n",
+                    k + 1,
+                    format_conditions(rule, feature_names),
+                    target_names[rule.prediction.index()],
+                    rule.correct,
+                    rule.covered
+                )
+            })
+            .collect()
+    }
+
+    /// Markdown table of the rules, like [RuleListClassifier::to_text].
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn to_markdown(&self, feature_names: &[&str], target_names: &[&str]) -> String {
+        let escape = |text: String| text.replace('|', "\\|");
+        let mut table = String::from(
+            "| Rule | Conditions | Prediction | Correct | Covered |\n\
+             | ---: | --- | --- | ---: | ---: |\n",
+        );
+        for (k, rule) in self
+            .named_rules(feature_names, target_names)
+            .iter()
+            .enumerate()
+        {
+            table += &format!(
+                "| {} | {} | {} | {} | {} |\n",
+                k + 1,
+                escape(format_conditions(rule, feature_names)),
+                escape(target_names[rule.prediction.index()].to_string()),
+                rule.correct,
+                rule.covered
+            );
+        }
+        table
+    }
+
+    /// Graphviz DOT graph of the rules: a chain of conditions, each with a "yes" edge to its
+    /// prediction and a "no" edge to the next rule.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn to_dot(&self, feature_names: &[&str], target_names: &[&str]) -> String {
+        let escape = |text: String| text.replace('\\', "\\\\").replace('"', "\\\"");
+        let rules = self.named_rules(feature_names, target_names);
+        let last = rules.len();
+
+        let mut dot = String::from("digraph rules {\n    node [shape=box];\n");
+        for (k, rule) in rules.iter().enumerate().map(|(k, rule)| (k + 1, rule)) {
+            dot += &format!(
+                "    prediction{k} [label=\"{}\\n{}/{}\", shape=ellipse];\n",
+                escape(target_names[rule.prediction.index()].to_string()),
+                rule.correct,
+                rule.covered
+            );
+            if k == last {
+                break;
+            }
+            let conditions: Vec<String> = rule
+                .conditions
+                .iter()
+                .map(|condition| escape(format_condition(condition, feature_names)))
+                .collect();
+            dot += &format!("    rule{k} [label=\"{}\"];\n", conditions.join("\\nand "));
+            dot += &format!("    rule{k} -> prediction{k} [label=\"yes\"];\n");
+            let next = if k + 1 == last {
+                format!("prediction{last}")
+            } else {
+                format!("rule{}", k + 1)
+            };
+            dot += &format!("    rule{k} -> {next} [label=\"no\"];\n");
+        }
+        dot += "}\n";
+        dot
+    }
+}
+
+#[test]
+fn iris_golden_files() {
+    use crate::datasets::iris::{self, Iris, Species};
+    use crate::datasets::Dataset;
+    use crate::metrics::classification::accuracy;
+
+    let (x, y) = Iris::to_xy(iris::DATA);
+    let mut model = RuleListClassifier::<Species>::new();
+    model.fit(&x, &y);
+    assert!(accuracy(&y, &model.predict(&x)) > 0.95);
+
+    let (features, targets) = (&Iris::FEATURE_NAMES, &Iris::TARGET_NAMES);
+    assert_eq!(
+        model.to_text(features, targets),
+        include_str!("golden/iris.txt")
+    );
+    assert_eq!(
+        model.to_markdown(features, targets),
+        include_str!("golden/iris.md")
+    );
+    assert_eq!(
+        model.to_dot(features, targets),
+        include_str!("golden/iris.dot")
+    );
+}
+
+#[test]
+fn thresholds_are_rounded() {
+    assert_eq!(format_threshold(16.795), "16.8");
+    assert_eq!(format_threshold(2.45), "2.45");
+    assert_eq!(format_threshold(0.05257), "0.0526");
+    assert_eq!(format_threshold(697.8), "698");
+    assert_eq!(format_threshold(-1.5), "-1.5");
+}
@@ -0,0 +1,306 @@
+use std::marker::PhantomData;
+
+use crate::datasets::ClassLabel;
+use crate::matrix::Matrix;
+
+mod bins;
+mod export;
+
+pub use bins::TargetBins;
+
+/// Side of the threshold that a [Condition] requires.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    AtMost,
+    Above,
+}
+
+/// Test of a single feature against a threshold, e.g. `worst radius > 16.8`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Condition {
+    pub feature: usize,
+    pub comparison: Comparison,
+    pub threshold: f32,
+}
+
+impl Condition {
+    pub fn matches(&self, row: &[f32]) -> bool {
+        match self.comparison {
+            Comparison::AtMost => row[self.feature] <= self.threshold,
+            Comparison::Above => row[self.feature] > self.threshold,
+        }
+    }
+}
+
+/// A conjunction of conditions and the class it predicts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rule<L> {
+    /// Conditions that must all hold. The final, default rule has none.
+    pub conditions: Vec<Condition>,
+    pub prediction: L,
+
+    /// Training samples that reach the rule (i.e. that no earlier rule covers) and satisfy it.
+    pub covered: usize,
+
+    /// Covered training samples whose label is the prediction.
+    pub correct: usize,
+}
+
+impl<L> Rule<L> {
+    pub fn matches(&self, row: &[f32]) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(row))
+    }
+}
+
+/// Ordered list of `if ... then class` rules learned by sequential covering, in the style of
+/// RIPPER: the rules of each class are grown one at a time, from the rarest class to the most
+/// frequent one, and the samples a rule covers are removed before growing the next rule. The
+/// first matching rule classifies a sample, and a final default rule catches everything else.
+///
+/// Each rule is grown greedily by adding the condition with the best FOIL information gain, and
+/// is only kept if it is right for more than half of the samples it covers.
+///
+/// A continuous target, such as that of `diabetes::DATA`, is first split into ranges with
+/// [TargetBins].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleListClassifier<L> {
+    max_conditions: usize,
+    min_coverage: usize,
+    max_rules: Option<usize>,
+    fitted: Option<Vec<Rule<L>>>,
+    labels: PhantomData<L>,
+}
+
+impl<L: ClassLabel> RuleListClassifier<L> {
+    pub fn new() -> Self {
+        Self {
+            max_conditions: 3,
+            min_coverage: 2,
+            max_rules: None,
+            fitted: None,
+            labels: PhantomData,
+        }
+    }
+
+    /// Maximum number of conditions in each rule (3 by default). A single condition gives
+    /// OneR-style rules.
+    pub fn with_max_conditions(mut self, max_conditions: usize) -> Self {
+        assert!(max_conditions >= 1, "Rules need at least one condition");
+        self.max_conditions = max_conditions;
+        self
+    }
+
+    /// Minimum number of samples of its class that a rule must cover (2 by default).
+    pub fn with_min_coverage(mut self, min_coverage: usize) -> Self {
+        self.min_coverage = min_coverage.max(1);
+        self
+    }
+
+    /// Maximum number of rules, not counting the default rule (unlimited by default).
+    pub fn with_max_rules(mut self, max_rules: usize) -> Self {
+        self.max_rules = Some(max_rules);
+        self
+    }
+
+    /// Learns the rules from the rows of `x` and their labels `y`.
+    pub fn fit(&mut self, x: &Matrix, y: &[L]) {
+        assert_eq!(x.num_rows(), y.len(), "Inconsistent number of samples");
+        assert!(!y.is_empty(), "No samples");
+        let classes: Vec<usize> = y.iter().map(ClassLabel::index).collect();
+        let num_classes = classes.iter().max().unwrap() + 1;
+
+        let mut counts = vec![0; num_classes];
+        classes.iter().for_each(|&k| counts[k] += 1);
+        let mut order: Vec<usize> = (0..num_classes).filter(|&k| counts[k] > 0).collect();
+        order.sort_by_key(|&k| counts[k]);
+        let most_frequent = order.pop().unwrap();
+
+        let mut remaining: Vec<usize> = (0..y.len()).collect();
+        let mut rules = Vec::new();
+        'classes: for class in order {
+            while remaining.iter().any(|&i| classes[i] == class) {
+                if self.max_rules.is_some_and(|max| rules.len() >= max) {
+                    break 'classes;
+                }
+                let conditions = self.grow_rule(x, &classes, &remaining, class);
+                let covered: Vec<usize> = remaining
+                    .iter()
+                    .copied()
+                    .filter(|&i| conditions.iter().all(|c| c.matches(x.row(i))))
+                    .collect();
+                let correct = covered.iter().filter(|&&i| classes[i] == class).count();
+                if conditions.is_empty()
+                    || correct < self.min_coverage
+                    || 2 * correct <= covered.len()
+                {
+                    break;
+                }
+
+                remaining.retain(|i| !covered.contains(i));
+                rules.push(Rule {
+                    conditions,
+                    prediction: L::from_index(class),
+                    covered: covered.len(),
+                    correct,
+                });
+            }
+        }
+
+        // The default rule predicts the majority of the samples that no rule covers.
+        let mut left = vec![0; num_classes];
+        remaining.iter().for_each(|&i| left[classes[i]] += 1);
+        let default = match left.iter().max() {
+            Some(&max) if max > 0 => left.iter().position(|&count| count == max).unwrap(),
+            _ => most_frequent,
+        };
+        rules.push(Rule {
+            conditions: Vec::new(),
+            prediction: L::from_index(default),
+            covered: remaining.len(),
+            correct: left[default],
+        });
+        self.fitted = Some(rules);
+    }
+
+    /// Adds conditions one at a time, each with the best FOIL gain for `class` on the samples
+    /// that the rule still covers.
+    fn grow_rule(
+        &self,
+        x: &Matrix,
+        classes: &[usize],
+        samples: &[usize],
+        class: usize,
+    ) -> Vec<Condition> {
+        let mut conditions: Vec<Condition> = Vec::new();
+        let mut covered = samples.to_vec();
+
+        while conditions.len() < self.max_conditions {
+            let positives = covered.iter().filter(|&&i| classes[i] == class).count();
+            if positives == covered.len() {
+                break;
+            }
+            let Some(best) = best_condition(x, classes, &covered, class, self.min_coverage) else {
+                break;
+            };
+
+            // A tighter threshold on the same side of a feature replaces the looser one.
+            match conditions
+                .iter_mut()
+                .find(|c| c.feature == best.feature && c.comparison == best.comparison)
+            {
+                Some(existing) => *existing = best,
+                None => conditions.push(best),
+            }
+            covered.retain(|&i| best.matches(x.row(i)));
+        }
+        conditions
+    }
+
+    /// The learned rules in order, ending with the default rule, once fitted.
+    pub fn rules(&self) -> Option<&[Rule<L>]> {
+        self.fitted.as_deref()
+    }
+
+    /// Predicts the class of each row of `x` with the first rule that matches it.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<L> {
+        let rules = self.fitted.as_ref().expect("Model not fitted");
+        x.rows()
+            .map(|row| {
+                let rule = rules.iter().find(|rule| rule.matches(row)).unwrap();
+                rule.prediction
+            })
+            .collect()
+    }
+}
+
+impl<L: ClassLabel> Default for RuleListClassifier<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Condition with the highest FOIL gain `p1 (log2(p1 / (p1 + n1)) - log2(p0 / (p0 + n0)))`, where
+/// `p0, n0` count the positive and negative samples covered before adding the condition, and
+/// `p1, n1` after. Ties go to the first feature, then to the lowest threshold.
+fn best_condition(
+    x: &Matrix,
+    classes: &[usize],
+    samples: &[usize],
+    class: usize,
+    min_coverage: usize,
+) -> Option<Condition> {
+    let is_positive = |i: usize| classes[i] == class;
+    let total_positives = samples.iter().filter(|&&i| is_positive(i)).count();
+    let base = (total_positives as f64 / samples.len() as f64).log2();
+    let gain = |p: usize, n: usize| {
+        if p < min_coverage.max(1) {
+            f64::NEG_INFINITY
+        } else {
+            p as f64 * ((p as f64 / (p + n) as f64).log2() - base)
+        }
+    };
+
+    let mut best: Option<(f64, Condition)> = None;
+    for feature in 0..x.num_cols() {
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|&a, &b| x[(a, feature)].total_cmp(&x[(b, feature)]));
+
+        // Positive and negative counts at or below each threshold.
+        let (mut p, mut n) = (0, 0);
+        for k in 0..sorted.len() - 1 {
+            if is_positive(sorted[k]) {
+                p += 1;
+            } else {
+                n += 1;
+            }
+            let (below, above) = (x[(sorted[k], feature)], x[(sorted[k + 1], feature)]);
+            if below == above {
+                continue;
+            }
+            let middle = below + (above - below) / 2.0;
+            let threshold = if middle < above { middle } else { below };
+
+            let sides = [
+                (Comparison::AtMost, p, n),
+                (
+                    Comparison::Above,
+                    total_positives - p,
+                    sorted.len() - k - 1 - (total_positives - p),
+                ),
+            ];
+            for (comparison, p, n) in sides {
+                let gain = gain(p, n);
+                if gain > 0.0 && best.as_ref().is_none_or(|(best, _)| gain > *best) {
+                    let condition = Condition {
+                        feature,
+                        comparison,
+                        threshold,
+                    };
+                    best = Some((gain, condition));
+                }
+            }
+        }
+    }
+    best.map(|(_, condition)| condition)
+}
+
+#[test]
+fn breast_cancer_rules() {
+    use crate::datasets::breast_cancer::{self, BreastCancer, Label};
+    use crate::datasets::Dataset;
+    use crate::metrics::classification::accuracy;
+
+    let (x, y) = BreastCancer::to_xy(&breast_cancer::DATA);
+    let mut model = RuleListClassifier::<Label>::new().with_min_coverage(10);
+    model.fit(&x, &y);
+    assert!(accuracy(&y, &model.predict(&x)) > 0.95);
+
+    assert_eq!(
+        model.to_text(&BreastCancer::FEATURE_NAMES, &BreastCancer::TARGET_NAMES),
+        include_str!("golden/breast_cancer.txt")
+    );
+}
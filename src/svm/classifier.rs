@@ -0,0 +1,233 @@
+use std::marker::PhantomData;
+
+use super::{smo, svm_params_builders, KernelFn, Support, SvmParams};
+use crate::datasets::ClassLabel;
+use crate::matrix::Matrix;
+
+/// Support vector classifier: finds the maximum-margin boundary between two classes in the
+/// feature space of a kernel, and combines the classifiers of every pair of classes by voting
+/// (one-vs-one).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SVC<L> {
+    params: SvmParams,
+    fitted: Option<SvcFit>,
+    labels: PhantomData<L>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct SvcFit {
+    support: Support,
+
+    /// Classes `(a, b)` of each binary classifier, whose positive decisions favour `b`.
+    pairs: Vec<(usize, usize)>,
+
+    /// One row per pair, one column per support vector.
+    dual_coef: Matrix<f64>,
+    intercept: Vec<f64>,
+}
+
+impl<L: ClassLabel> SVC<L> {
+    /// RBF kernel with `C = 1`.
+    pub fn new() -> Self {
+        Self {
+            params: SvmParams::default(),
+            fitted: None,
+            labels: PhantomData,
+        }
+    }
+
+    svm_params_builders!();
+
+    /// Fits a binary classifier to the rows of `x` for every pair of classes in their labels
+    /// `y`.
+    pub fn fit(&mut self, x: &Matrix, y: &[L]) {
+        assert_eq!(x.num_rows(), y.len(), "Inconsistent number of samples");
+        let kernel = KernelFn::new(&self.params, x);
+        let gram = kernel.gram(x);
+
+        let mut classes: Vec<usize> = y.iter().map(ClassLabel::index).collect();
+        classes.sort();
+        classes.dedup();
+        assert!(classes.len() >= 2, "At least two classes are needed");
+
+        let mut pairs = Vec::new();
+        let mut coefs: Vec<Vec<(usize, f64)>> = Vec::new();
+        let mut intercept = Vec::new();
+        for (k, &a) in classes.iter().enumerate() {
+            for &b in &classes[k + 1..] {
+                let samples: Vec<usize> = (0..y.len())
+                    .filter(|&i| y[i].index() == a || y[i].index() == b)
+                    .collect();
+                let signs: Vec<f64> = samples
+                    .iter()
+                    .map(|&i| if y[i].index() == b { 1.0 } else { -1.0 })
+                    .collect();
+                let sub_gram = Matrix::from_rows(
+                    &samples
+                        .iter()
+                        .map(|&i| samples.iter().map(|&j| gram[(i, j)]).collect())
+                        .collect::<Vec<Vec<f64>>>(),
+                );
+                let solution = smo::solve(
+                    &sub_gram,
+                    &signs,
+                    &vec![-1.0; samples.len()],
+                    self.params.c,
+                    self.params.tol,
+                    self.params.max_iter,
+                );
+
+                pairs.push((a, b));
+                coefs.push(
+                    samples
+                        .iter()
+                        .zip(signs.iter().zip(&solution.alpha))
+                        .filter(|(_, (_, &alpha))| alpha > 0.0)
+                        .map(|(&i, (sign, alpha))| (i, sign * alpha))
+                        .collect(),
+                );
+                intercept.push(-solution.rho);
+            }
+        }
+
+        let mut indices: Vec<usize> = coefs.iter().flatten().map(|&(i, _)| i).collect();
+        indices.sort();
+        indices.dedup();
+        let mut dual_coef = Matrix::zeros(pairs.len(), indices.len());
+        for (p, coefs) in coefs.iter().enumerate() {
+            for &(i, coef) in coefs {
+                dual_coef[(p, indices.binary_search(&i).unwrap())] = coef;
+            }
+        }
+
+        self.fitted = Some(SvcFit {
+            support: Support::new(x, indices, kernel),
+            pairs,
+            dual_coef,
+            intercept,
+        });
+    }
+
+    fn fitted(&self) -> &SvcFit {
+        self.fitted.as_ref().expect("Model not fitted")
+    }
+
+    /// Returns the decision of each binary classifier (columns, in the order of
+    /// [class_pairs](Self::class_pairs)) for each row of `x`: positive values favour the second
+    /// class of the pair.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn decision_function(&self, x: &Matrix) -> Matrix<f64> {
+        let fitted = self.fitted();
+        let mut decisions = Matrix::zeros(x.num_rows(), fitted.pairs.len());
+        for (i, row) in x.rows().enumerate() {
+            let kernels = fitted.support.kernels(row);
+            for (p, coefs) in fitted.dual_coef.rows().enumerate() {
+                let sum: f64 = coefs.iter().zip(&kernels).map(|(c, k)| c * k).sum();
+                decisions[(i, p)] = sum + fitted.intercept[p];
+            }
+        }
+        decisions
+    }
+
+    /// Predicts the class of each row of `x` that wins the most pairwise decisions, the first
+    /// one in [ClassLabel::index] order on ties.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<L> {
+        let fitted = self.fitted();
+        let num_classes = fitted.pairs.iter().map(|&(_, b)| b + 1).max().unwrap();
+        self.decision_function(x)
+            .rows()
+            .map(|decisions| {
+                let mut votes = vec![0; num_classes];
+                for (&(a, b), &decision) in fitted.pairs.iter().zip(decisions) {
+                    votes[if decision > 0.0 { b } else { a }] += 1;
+                }
+                let winner = (0..num_classes).rev().max_by_key(|&k| votes[k]).unwrap();
+                L::from_index(winner)
+            })
+            .collect()
+    }
+
+    /// Classes of each binary classifier, for every pair of classes seen in training, once
+    /// fitted.
+    pub fn class_pairs(&self) -> Option<Vec<(L, L)>> {
+        let fitted = self.fitted.as_ref()?;
+        Some(
+            fitted
+                .pairs
+                .iter()
+                .map(|&(a, b)| (L::from_index(a), L::from_index(b)))
+                .collect(),
+        )
+    }
+
+    /// Coefficient of each support vector (columns) in the decision function of each binary
+    /// classifier (rows): its dual variable, signed by its class, or 0 if it does not belong to
+    /// the pair. Once fitted.
+    pub fn dual_coef(&self) -> Option<&Matrix<f64>> {
+        self.fitted.as_ref().map(|fitted| &fitted.dual_coef)
+    }
+
+    /// Constant term of the decision function of each binary classifier, once fitted.
+    pub fn intercept(&self) -> Option<&[f64]> {
+        self.fitted.as_ref().map(|fitted| &fitted.intercept[..])
+    }
+}
+
+impl<L: ClassLabel> Default for SVC<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn separable_points() {
+    use super::Kernel;
+
+    // The margin lies between (0, 0) and (2, 2), with the boundary `x0 + x1 = 2`.
+    let x = Matrix::new(4, 2, vec![-1.0, -1.0, 0.0, 0.0, 2.0, 2.0, 3.0, 3.0]);
+    let y: [u8; 4] = [0, 0, 1, 1];
+    let mut model = SVC::new().with_kernel(Kernel::Linear).with_c(10.0);
+    model.fit(&x, &y);
+
+    assert_eq!(model.support(), Some(&[1, 2][..]));
+    let dual_coef = model.dual_coef().unwrap();
+    assert!((dual_coef[(0, 0)] + 0.25).abs() < 1e-6 && (dual_coef[(0, 1)] - 0.25).abs() < 1e-6);
+    assert!((model.intercept().unwrap()[0] + 1.0).abs() < 1e-6);
+    assert_eq!(model.predict(&Matrix::new(1, 2, vec![0.9, 1.2])), [1]);
+}
+
+#[test]
+fn iris_and_digits() {
+    use super::Kernel;
+    use crate::datasets::digits::{self, Digits};
+    use crate::datasets::iris::{self, Iris};
+    use crate::datasets::Dataset;
+    use crate::metrics::classification::accuracy;
+    use crate::model_selection::train_test_split;
+
+    let (x, y) = Iris::to_xy(iris::DATA);
+    for kernel in [
+        Kernel::Linear,
+        Kernel::Polynomial {
+            degree: 3,
+            coef0: 1.0,
+        },
+        Kernel::Rbf,
+    ] {
+        let mut model = SVC::new().with_kernel(kernel);
+        model.fit(&x, &y);
+        assert!(accuracy(&y, &model.predict(&x)) > 0.96, "{kernel:?}");
+        assert_eq!(model.dual_coef().unwrap().num_rows(), 3);
+    }
+
+    let (train, test) = train_test_split(&digits::DATA, 0.25, 0, true);
+    let (x_train, y_train) = Digits::to_xy(&train);
+    let (x_test, y_test) = Digits::to_xy(&test);
+    let mut model = SVC::new();
+    model.fit(&x_train, &y_train);
+    assert!(accuracy(&y_test, &model.predict(&x_test)) > 0.97);
+    assert_eq!(model.class_pairs().unwrap().len(), 45);
+}
@@ -0,0 +1,241 @@
+use crate::matrix::Matrix;
+
+mod classifier;
+mod regressor;
+mod smo;
+
+pub use classifier::SVC;
+pub use regressor::SVR;
+
+/// Kernel function `K(a, b)` of a support vector machine, with `gamma` set by [Gamma].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Kernel {
+    /// `a · b`.
+    Linear,
+
+    /// `(gamma a · b + coef0)^degree`.
+    Polynomial { degree: i32, coef0: f64 },
+
+    /// `exp(-gamma |a - b|^2)`, the default.
+    #[default]
+    Rbf,
+
+    /// `tanh(gamma a · b + coef0)`.
+    Sigmoid { coef0: f64 },
+}
+
+/// Scale `gamma` of the non-linear kernels.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Gamma {
+    /// `1 / (num_features * variance)`, where the variance is that of every value of the
+    /// training matrix (the default).
+    #[default]
+    Scale,
+
+    /// `1 / num_features`.
+    Auto,
+
+    Value(f64),
+}
+
+impl Gamma {
+    fn of(&self, x: &Matrix) -> f64 {
+        let num_features = x.num_cols() as f64;
+        match *self {
+            Gamma::Scale => {
+                let values = x.as_slice();
+                let n = values.len() as f64;
+                let mean = values.iter().map(|&v| v as f64).sum::<f64>() / n;
+                let variance = values
+                    .iter()
+                    .map(|&v| (v as f64 - mean).powi(2))
+                    .sum::<f64>()
+                    / n;
+                if variance > 0.0 {
+                    1.0 / (num_features * variance)
+                } else {
+                    1.0
+                }
+            }
+            Gamma::Auto => 1.0 / num_features,
+            Gamma::Value(gamma) => gamma,
+        }
+    }
+}
+
+/// Settings shared by [SVC] and [SVR].
+#[derive(Clone, Debug, PartialEq)]
+struct SvmParams {
+    kernel: Kernel,
+    gamma: Gamma,
+    c: f64,
+    tol: f64,
+    max_iter: usize,
+}
+
+impl Default for SvmParams {
+    fn default() -> Self {
+        Self {
+            kernel: Kernel::Rbf,
+            gamma: Gamma::Scale,
+            c: 1.0,
+            tol: 1e-3,
+            max_iter: 1_000_000,
+        }
+    }
+}
+
+/// Builder methods for the [SvmParams] in the `params` field of an estimator.
+macro_rules! svm_params_builders {
+    () => {
+        /// Kernel function ([RBF](super::Kernel::Rbf) by default).
+        pub fn with_kernel(mut self, kernel: super::Kernel) -> Self {
+            self.params.kernel = kernel;
+            self
+        }
+
+        /// Scale of the non-linear kernels ([Scale](super::Gamma::Scale) by default).
+        pub fn with_gamma(mut self, gamma: super::Gamma) -> Self {
+            self.params.gamma = gamma;
+            self
+        }
+
+        /// Penalty on the samples that violate the margin (1 by default): larger values fit the
+        /// training set more closely.
+        pub fn with_c(mut self, c: f64) -> Self {
+            assert!(c > 0.0, "Invalid C");
+            self.params.c = c;
+            self
+        }
+
+        /// Tolerance on the violation of the optimality conditions (1e-3 by default).
+        pub fn with_tol(mut self, tol: f64) -> Self {
+            self.params.tol = tol;
+            self
+        }
+
+        /// Maximum number of SMO iterations (a million by default).
+        pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+            self.params.max_iter = max_iter;
+            self
+        }
+
+        /// Indices of the training samples that are support vectors, once fitted.
+        pub fn support(&self) -> Option<&[usize]> {
+            self.fitted
+                .as_ref()
+                .map(|fitted| &fitted.support.indices[..])
+        }
+
+        /// The support vectors, in the order of [support](Self::support), once fitted.
+        pub fn support_vectors(&self) -> Option<&Matrix> {
+            self.fitted.as_ref().map(|fitted| &fitted.support.vectors)
+        }
+    };
+}
+
+use svm_params_builders;
+
+/// A kernel function with its `gamma` resolved.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct KernelFn {
+    kernel: Kernel,
+    gamma: f64,
+}
+
+impl KernelFn {
+    fn new(params: &SvmParams, x: &Matrix) -> Self {
+        Self {
+            kernel: params.kernel,
+            gamma: params.gamma.of(x),
+        }
+    }
+
+    fn eval(&self, a: &[f32], b: &[f32]) -> f64 {
+        let dot = || {
+            a.iter()
+                .zip(b)
+                .map(|(&a, &b)| a as f64 * b as f64)
+                .sum::<f64>()
+        };
+        match self.kernel {
+            Kernel::Linear => dot(),
+            Kernel::Polynomial { degree, coef0 } => (self.gamma * dot() + coef0).powi(degree),
+            Kernel::Rbf => {
+                let distance: f64 = a
+                    .iter()
+                    .zip(b)
+                    .map(|(&a, &b)| (a as f64 - b as f64).powi(2))
+                    .sum();
+                (-self.gamma * distance).exp()
+            }
+            Kernel::Sigmoid { coef0 } => (self.gamma * dot() + coef0).tanh(),
+        }
+    }
+
+    /// Kernel of every pair of rows of `x`.
+    fn gram(&self, x: &Matrix) -> Matrix<f64> {
+        let n = x.num_rows();
+        let mut gram = Matrix::zeros(n, n);
+        for i in 0..n {
+            for j in 0..=i {
+                let value = self.eval(x.row(i), x.row(j));
+                gram[(i, j)] = value;
+                gram[(j, i)] = value;
+            }
+        }
+        gram
+    }
+}
+
+/// The training samples with a non-zero dual coefficient, which the predictions depend on.
+#[derive(Clone, Debug, PartialEq)]
+struct Support {
+    indices: Vec<usize>,
+    vectors: Matrix,
+    kernel: KernelFn,
+}
+
+impl Support {
+    fn new(x: &Matrix, indices: Vec<usize>, kernel: KernelFn) -> Self {
+        Self {
+            vectors: x.select_rows(&indices),
+            indices,
+            kernel,
+        }
+    }
+
+    /// Kernel of `row` with each support vector.
+    fn kernels(&self, row: &[f32]) -> Vec<f64> {
+        assert_eq!(
+            row.len(),
+            self.vectors.num_cols(),
+            "Invalid number of columns"
+        );
+        self.vectors
+            .rows()
+            .map(|vector| self.kernel.eval(vector, row))
+            .collect()
+    }
+}
+
+#[test]
+fn kernels() {
+    let x = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 0.0]);
+    let kernel = |kernel| KernelFn { kernel, gamma: 0.5 };
+    let (a, b) = (x.row(0), x.row(1));
+
+    assert_eq!(kernel(Kernel::Linear).eval(a, b), 3.0);
+    let polynomial = Kernel::Polynomial {
+        degree: 2,
+        coef0: 1.0,
+    };
+    assert_eq!(kernel(polynomial).eval(a, b), 6.25);
+    assert_eq!(kernel(Kernel::Rbf).eval(a, b), (-4.0f64).exp());
+    assert_eq!(kernel(Kernel::Sigmoid { coef0: -1.5 }).eval(a, b), 0.0);
+    assert_eq!(kernel(Kernel::Rbf).gram(&x)[(1, 1)], 1.0);
+
+    // Four values with a variance of 1.25.
+    assert_eq!(Gamma::Scale.of(&x), 0.4);
+    assert_eq!(Gamma::Auto.of(&x), 0.5);
+}
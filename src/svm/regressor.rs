@@ -0,0 +1,146 @@
+use super::{smo, svm_params_builders, KernelFn, Support, SvmParams};
+use crate::matrix::Matrix;
+
+/// Support vector regressor: fits the flattest function, in the feature space of a kernel, that
+/// predicts each target within `epsilon`, with a penalty `C` on the larger errors.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SVR {
+    params: SvmParams,
+    epsilon: f64,
+    fitted: Option<SvrFit>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct SvrFit {
+    support: Support,
+    dual_coef: Vec<f64>,
+    intercept: f64,
+}
+
+impl SVR {
+    /// RBF kernel with `C = 1` and `epsilon = 0.1`.
+    pub fn new() -> Self {
+        Self {
+            params: SvmParams::default(),
+            epsilon: 0.1,
+            fitted: None,
+        }
+    }
+
+    svm_params_builders!();
+
+    /// Half-width of the tube around the targets within which errors are not penalized (0.1
+    /// by default).
+    pub fn with_epsilon(mut self, epsilon: f64) -> Self {
+        assert!(epsilon >= 0.0, "Invalid epsilon");
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Fits the regressor to the rows of `x` and their targets `y`.
+    pub fn fit<Y: Copy + Into<f64>>(&mut self, x: &Matrix, y: &[Y]) {
+        assert_eq!(x.num_rows(), y.len(), "Inconsistent number of samples");
+        let n = y.len();
+        let epsilon = self.epsilon;
+        let kernel = KernelFn::new(&self.params, x);
+
+        // Each sample has one variable for errors above the tube, and one for errors below it.
+        let signs: Vec<f64> = [1.0, -1.0].iter().flat_map(|&sign| vec![sign; n]).collect();
+        let linear: Vec<f64> = [-1.0, 1.0]
+            .iter()
+            .flat_map(|&sign| y.iter().map(move |&v| epsilon + sign * v.into()))
+            .collect();
+        let solution = smo::solve(
+            &kernel.gram(x),
+            &signs,
+            &linear,
+            self.params.c,
+            self.params.tol,
+            self.params.max_iter,
+        );
+
+        let coefs: Vec<f64> = (0..n)
+            .map(|i| solution.alpha[i] - solution.alpha[i + n])
+            .collect();
+        let indices: Vec<usize> = (0..n).filter(|&i| coefs[i] != 0.0).collect();
+        self.fitted = Some(SvrFit {
+            dual_coef: indices.iter().map(|&i| coefs[i]).collect(),
+            support: Support::new(x, indices, kernel),
+            intercept: -solution.rho,
+        });
+    }
+
+    /// Predicts the target of each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<f32> {
+        let fitted = self.fitted.as_ref().expect("Model not fitted");
+        x.rows()
+            .map(|row| {
+                let kernels = fitted.support.kernels(row);
+                let sum: f64 = fitted
+                    .dual_coef
+                    .iter()
+                    .zip(&kernels)
+                    .map(|(c, k)| c * k)
+                    .sum();
+                (sum + fitted.intercept) as f32
+            })
+            .collect()
+    }
+
+    /// Coefficient of each support vector in the prediction, once fitted.
+    pub fn dual_coef(&self) -> Option<&[f64]> {
+        self.fitted.as_ref().map(|fitted| &fitted.dual_coef[..])
+    }
+
+    /// Constant term of the prediction, once fitted.
+    pub fn intercept(&self) -> Option<f64> {
+        self.fitted.as_ref().map(|fitted| fitted.intercept)
+    }
+}
+
+impl Default for SVR {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn sine_and_line() {
+    use super::Kernel;
+    use crate::metrics::regression::r2_score;
+
+    let inputs: Vec<f32> = (0..60).map(|i| i as f32 / 10.0).collect();
+    let x = Matrix::from_column(&inputs);
+    let y: Vec<f32> = inputs.iter().map(|v| v.sin()).collect();
+
+    let mut model = SVR::new().with_c(10.0);
+    model.fit(&x, &y);
+    let predictions = model.predict(&x);
+    assert!(r2_score(&y, &predictions) > 0.97);
+
+    // Only samples on or outside the tube are support vectors.
+    let support = model.support().unwrap();
+    assert!(support.len() < 60);
+    for i in (0..60).filter(|i| !support.contains(i)) {
+        assert!((y[i] - predictions[i]).abs() <= 0.1 + 1e-3);
+    }
+
+    // The flattest line within 0.5 of `2 x + 1` on `[0, 5.9]` lies 0.5 above it at 0 and 0.5
+    // below it at 5.9.
+    let y: Vec<f32> = inputs.iter().map(|v| 2.0 * v + 1.0).collect();
+    let mut model = SVR::new()
+        .with_kernel(Kernel::Linear)
+        .with_epsilon(0.5)
+        .with_c(100.0);
+    model.fit(&x, &y);
+    let slope: f64 = model
+        .dual_coef()
+        .unwrap()
+        .iter()
+        .zip(model.support_vectors().unwrap().rows())
+        .map(|(c, row)| c * row[0] as f64)
+        .sum();
+    assert!((slope - (2.0 - 1.0 / 5.9)).abs() < 1e-3, "{slope}");
+}
@@ -0,0 +1,180 @@
+use crate::matrix::Matrix;
+
+/// Floor of the curvature along a working pair, for kernels that are not positive definite.
+const TAU: f64 = 1e-12;
+
+/// Solution of the dual problem solved by [solve].
+pub(super) struct Solution {
+    pub(super) alpha: Vec<f64>,
+
+    /// Offset subtracted from the decision function.
+    pub(super) rho: f64,
+}
+
+/// Minimizes `alpha^T Q alpha / 2 + linear^T alpha` subject to `signs^T alpha = 0` and
+/// `0 <= alpha <= c`, where `Q[(s, t)] = signs[s] signs[t] kernel[(s % n, t % n)]` for an `n` by
+/// `n` kernel matrix (so that regression can use two variables per sample).
+///
+/// This is the sequential minimal optimization of LIBSVM: each iteration optimizes the pair of
+/// variables chosen with second-order information, until the largest violation of the
+/// optimality conditions is below `tol`.
+pub(super) fn solve(
+    kernel: &Matrix<f64>,
+    signs: &[f64],
+    linear: &[f64],
+    c: f64,
+    tol: f64,
+    max_iter: usize,
+) -> Solution {
+    let n = kernel.num_rows();
+    let m = signs.len();
+    let q = |s: usize, t: usize| signs[s] * signs[t] * kernel[(s % n, t % n)];
+    let diagonal: Vec<f64> = (0..m).map(|t| q(t, t)).collect();
+
+    let mut alpha = vec![0.0; m];
+    let mut gradient = linear.to_vec();
+    let is_upper = |alpha: &[f64], t: usize| alpha[t] >= c;
+    let is_lower = |alpha: &[f64], t: usize| alpha[t] <= 0.0;
+
+    for _ in 0..max_iter {
+        // The variable that violates the optimality conditions the most...
+        let mut g_max = f64::NEG_INFINITY;
+        let mut i = None;
+        for t in 0..m {
+            let can_move = if signs[t] > 0.0 {
+                !is_upper(&alpha, t)
+            } else {
+                !is_lower(&alpha, t)
+            };
+            if can_move && -signs[t] * gradient[t] >= g_max {
+                g_max = -signs[t] * gradient[t];
+                i = Some(t);
+            }
+        }
+        let Some(i) = i else { break };
+
+        // ...and the one that, paired with it, decreases the objective the most.
+        let mut g_max2 = f64::NEG_INFINITY;
+        let mut j = None;
+        let mut min_decrease = f64::INFINITY;
+        for t in 0..m {
+            let can_move = if signs[t] > 0.0 {
+                !is_lower(&alpha, t)
+            } else {
+                !is_upper(&alpha, t)
+            };
+            if !can_move {
+                continue;
+            }
+            let violation = signs[t] * gradient[t];
+            g_max2 = g_max2.max(violation);
+            let difference = g_max + violation;
+            if difference > 0.0 {
+                let curvature = diagonal[i] + diagonal[t] - 2.0 * kernel[(i % n, t % n)];
+                let decrease = -difference * difference / curvature.max(TAU);
+                if decrease <= min_decrease {
+                    min_decrease = decrease;
+                    j = Some(t);
+                }
+            }
+        }
+        let Some(j) = j.filter(|_| g_max + g_max2 >= tol) else {
+            break;
+        };
+
+        let (old_i, old_j) = (alpha[i], alpha[j]);
+        let q_ij = q(i, j);
+        if signs[i] != signs[j] {
+            let curvature = (diagonal[i] + diagonal[j] + 2.0 * q_ij).max(TAU);
+            let delta = (-gradient[i] - gradient[j]) / curvature;
+            let difference = alpha[i] - alpha[j];
+            alpha[i] += delta;
+            alpha[j] += delta;
+            if difference > 0.0 {
+                if alpha[j] < 0.0 {
+                    alpha[j] = 0.0;
+                    alpha[i] = difference;
+                }
+            } else if alpha[i] < 0.0 {
+                alpha[i] = 0.0;
+                alpha[j] = -difference;
+            }
+            if difference > 0.0 {
+                if alpha[i] > c {
+                    alpha[i] = c;
+                    alpha[j] = c - difference;
+                }
+            } else if alpha[j] > c {
+                alpha[j] = c;
+                alpha[i] = c + difference;
+            }
+        } else {
+            let curvature = (diagonal[i] + diagonal[j] - 2.0 * q_ij).max(TAU);
+            let delta = (gradient[i] - gradient[j]) / curvature;
+            let sum = alpha[i] + alpha[j];
+            alpha[i] -= delta;
+            alpha[j] += delta;
+            if sum > c {
+                if alpha[i] > c {
+                    alpha[i] = c;
+                    alpha[j] = sum - c;
+                }
+                if alpha[j] > c {
+                    alpha[j] = c;
+                    alpha[i] = sum - c;
+                }
+            } else {
+                if alpha[j] < 0.0 {
+                    alpha[j] = 0.0;
+                    alpha[i] = sum;
+                }
+                if alpha[i] < 0.0 {
+                    alpha[i] = 0.0;
+                    alpha[j] = sum;
+                }
+            }
+        }
+
+        let (delta_i, delta_j) = (alpha[i] - old_i, alpha[j] - old_j);
+        for (t, gradient) in gradient.iter_mut().enumerate() {
+            *gradient += q(i, t) * delta_i + q(j, t) * delta_j;
+        }
+    }
+
+    Solution {
+        rho: offset(&alpha, &gradient, signs, c),
+        alpha,
+    }
+}
+
+/// Offset of the decision function: the mean of `signs[t] gradient[t]` over the free variables,
+/// which all equal it at the optimum, or the middle of the range allowed by the bounded ones.
+fn offset(alpha: &[f64], gradient: &[f64], signs: &[f64], c: f64) -> f64 {
+    let (mut upper, mut lower) = (f64::INFINITY, f64::NEG_INFINITY);
+    let (mut sum, mut num_free) = (0.0, 0);
+    for t in 0..alpha.len() {
+        let value = signs[t] * gradient[t];
+        let positive = signs[t] > 0.0;
+        if alpha[t] >= c {
+            if positive {
+                lower = lower.max(value);
+            } else {
+                upper = upper.min(value);
+            }
+        } else if alpha[t] <= 0.0 {
+            if positive {
+                upper = upper.min(value);
+            } else {
+                lower = lower.max(value);
+            }
+        } else {
+            sum += value;
+            num_free += 1;
+        }
+    }
+    if num_free > 0 {
+        sum / num_free as f64
+    } else {
+        (upper + lower) / 2.0
+    }
+}
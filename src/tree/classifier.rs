@@ -0,0 +1,164 @@
+use std::marker::PhantomData;
+
+use super::{
+    name_importances, tree_params_builders, Classes, ClassificationCriterion, Tree, TreeParams,
+};
+use crate::datasets::ClassLabel;
+use crate::matrix::Matrix;
+use crate::utils::random::Rng;
+
+/// CART decision tree classifier: recursively splits the samples on a feature threshold, choosing
+/// the split that most reduces the impurity of the classes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecisionTreeClassifier<L> {
+    criterion: ClassificationCriterion,
+    params: TreeParams,
+    seed: u64,
+    fitted: Option<Tree>,
+    labels: PhantomData<L>,
+}
+
+impl<L: ClassLabel> DecisionTreeClassifier<L> {
+    /// Fully grown tree with the [Gini](ClassificationCriterion::Gini) criterion.
+    pub fn new() -> Self {
+        Self {
+            criterion: ClassificationCriterion::Gini,
+            params: TreeParams::default(),
+            seed: 0,
+            fitted: None,
+            labels: PhantomData,
+        }
+    }
+
+    pub fn with_criterion(mut self, criterion: ClassificationCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+
+    tree_params_builders!();
+
+    /// Seed of the draw of features for each split, when not all of them are considered.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Fits the tree to the rows of `x` and their labels `y`.
+    ///
+    /// The classes are the labels up to the largest [ClassLabel::index] in `y`.
+    pub fn fit(&mut self, x: &Matrix, y: &[L]) {
+        assert_eq!(x.num_rows(), y.len(), "Inconsistent number of samples");
+        let classes: Vec<usize> = y.iter().map(ClassLabel::index).collect();
+        let criterion = Classes {
+            num_classes: classes.iter().max().map_or(0, |&k| k + 1),
+            classes: &classes,
+            criterion: self.criterion,
+        };
+        let samples = (0..y.len()).collect();
+        let mut rng = Rng::new(self.seed);
+        self.fitted = Some(Tree::build(x, &criterion, samples, &self.params, &mut rng));
+    }
+
+    fn tree(&self) -> &Tree {
+        self.fitted.as_ref().expect("Model not fitted")
+    }
+
+    /// Returns the fraction of each class (columns, in [ClassLabel::index] order) in the leaf of
+    /// each row of `x`.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict_proba(&self, x: &Matrix) -> Matrix<f64> {
+        let rows: Vec<&[f64]> = x.rows().map(|row| self.tree().value(row)).collect();
+        Matrix::from_rows(&rows)
+    }
+
+    /// Predicts the majority class in the leaf of each row of `x`, with ties going to the class
+    /// with the lowest index.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<L> {
+        x.rows()
+            .map(|row| {
+                let fractions = self.tree().value(row);
+                let best = fractions.iter().enumerate().fold(0, |best, (k, &v)| {
+                    if v > fractions[best] {
+                        k
+                    } else {
+                        best
+                    }
+                });
+                L::from_index(best)
+            })
+            .collect()
+    }
+
+    /// Share of the total impurity decrease brought by the splits on each feature, once fitted.
+    pub fn feature_importances(&self) -> Option<Vec<f64>> {
+        self.fitted.as_ref().map(Tree::importances)
+    }
+
+    /// Pairs each feature name, typically from the `FEATURE_NAMES` of the dataset, with its
+    /// importance, once fitted.
+    pub fn named_importances<'a>(&self, feature_names: &[&'a str]) -> Option<Vec<(&'a str, f64)>> {
+        Some(name_importances(self.feature_importances()?, feature_names))
+    }
+
+    /// Number of splits on the longest path from the root to a leaf, once fitted.
+    pub fn depth(&self) -> Option<usize> {
+        self.fitted.as_ref().map(Tree::depth)
+    }
+
+    /// Number of leaves, once fitted.
+    pub fn num_leaves(&self) -> Option<usize> {
+        self.fitted.as_ref().map(Tree::num_leaves)
+    }
+}
+
+impl<L: ClassLabel> Default for DecisionTreeClassifier<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn breast_cancer_tree() {
+    use crate::datasets::breast_cancer::{self, BreastCancer, Label};
+    use crate::datasets::Dataset;
+    use crate::metrics::classification::accuracy;
+
+    let (x, y) = BreastCancer::to_xy(&breast_cancer::DATA);
+
+    let mut full = DecisionTreeClassifier::new();
+    full.fit(&x, &y);
+    assert_eq!(accuracy(&y, &full.predict(&x)), 1.0);
+
+    // The root split of scikit-learn's tree.
+    let mut stump = DecisionTreeClassifier::<Label>::new().with_max_depth(1);
+    stump.fit(&x, &y);
+    let root = stump.fitted.as_ref().unwrap().nodes[0]
+        .split
+        .clone()
+        .unwrap();
+    assert_eq!(BreastCancer::FEATURE_NAMES[root.feature], "worst radius");
+    assert!((root.threshold - 16.795).abs() < 1e-4);
+    assert_eq!(stump.num_leaves(), Some(2));
+
+    let mut shallow = DecisionTreeClassifier::new()
+        .with_criterion(ClassificationCriterion::Entropy)
+        .with_max_depth(3)
+        .with_min_samples_leaf(5);
+    shallow.fit(&x, &y);
+    assert_eq!(shallow.depth(), Some(3));
+    assert!(accuracy(&y, &shallow.predict(&x)) > 0.95);
+
+    let importances = shallow
+        .named_importances(&BreastCancer::FEATURE_NAMES)
+        .unwrap();
+    assert!((importances.iter().map(|(_, v)| v).sum::<f64>() - 1.0).abs() < 1e-9);
+
+    let mut sampled = DecisionTreeClassifier::<Label>::new()
+        .with_max_features(super::MaxFeatures::Sqrt)
+        .with_seed(3);
+    sampled.fit(&x, &y);
+    assert_eq!(accuracy(&y, &sampled.predict(&x)), 1.0);
+}
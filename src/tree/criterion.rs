@@ -0,0 +1,284 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Impurity of the samples of a node, which splits try to reduce.
+pub(crate) trait Criterion {
+    /// Prediction of a leaf holding `samples`: the fraction of each class, or a single value.
+    fn node_value(&self, samples: &[usize]) -> Vec<f64>;
+
+    fn impurity(&self, samples: &[usize]) -> f64;
+
+    /// Returns the impurities of the left and right children of every split of `samples` (in
+    /// the order of the split feature), from `samples[..1]` and `samples[1..]` to
+    /// `samples[..n - 1]` and `samples[n - 1..]`.
+    fn split_impurities(&self, samples: &[usize]) -> Vec<(f64, f64)> {
+        let left = self.prefix_impurities(samples.iter().copied());
+        let mut right = self.prefix_impurities(samples.iter().rev().copied());
+        right.reverse();
+        left.into_iter()
+            .take(samples.len() - 1)
+            .zip(right.into_iter().skip(1))
+            .collect()
+    }
+
+    /// Returns the impurity of the first `k` samples, for every `k` from 1 to the number of
+    /// samples.
+    fn prefix_impurities(&self, samples: impl Iterator<Item = usize>) -> Vec<f64>;
+}
+
+/// Impurity measure of a classification tree.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClassificationCriterion {
+    /// Probability of misclassifying a sample labeled at random with the class distribution of
+    /// its node (the default).
+    #[default]
+    Gini,
+
+    /// Shannon entropy (in bits) of the class distribution.
+    Entropy,
+}
+
+impl ClassificationCriterion {
+    fn of(&self, counts: &[f64], total: f64) -> f64 {
+        match self {
+            ClassificationCriterion::Gini => {
+                1.0 - counts.iter().map(|c| (c / total).powi(2)).sum::<f64>()
+            }
+            ClassificationCriterion::Entropy => counts
+                .iter()
+                .filter(|&&c| c > 0.0)
+                .map(|c| -(c / total) * (c / total).log2())
+                .sum(),
+        }
+    }
+}
+
+/// Impurity measure of a regression tree.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RegressionCriterion {
+    /// Mean squared error from the mean, whose leaves predict the mean (the default).
+    #[default]
+    SquaredError,
+
+    /// Mean absolute error from the median, whose leaves predict the median. Less sensitive to
+    /// outliers, but slower to fit.
+    AbsoluteError,
+}
+
+/// Classification targets of the training samples, as class indices.
+pub(crate) struct Classes<'a> {
+    pub(crate) classes: &'a [usize],
+    pub(crate) num_classes: usize,
+    pub(crate) criterion: ClassificationCriterion,
+}
+
+impl Classes<'_> {
+    fn counts(&self, samples: &[usize]) -> Vec<f64> {
+        let mut counts = vec![0.0; self.num_classes];
+        for &i in samples {
+            counts[self.classes[i]] += 1.0;
+        }
+        counts
+    }
+}
+
+impl Criterion for Classes<'_> {
+    fn node_value(&self, samples: &[usize]) -> Vec<f64> {
+        let total = samples.len() as f64;
+        self.counts(samples).iter().map(|c| c / total).collect()
+    }
+
+    fn impurity(&self, samples: &[usize]) -> f64 {
+        self.criterion
+            .of(&self.counts(samples), samples.len() as f64)
+    }
+
+    fn prefix_impurities(&self, samples: impl Iterator<Item = usize>) -> Vec<f64> {
+        let mut counts = vec![0.0; self.num_classes];
+        samples
+            .enumerate()
+            .map(|(k, i)| {
+                counts[self.classes[i]] += 1.0;
+                self.criterion.of(&counts, (k + 1) as f64)
+            })
+            .collect()
+    }
+}
+
+/// Regression targets of the training samples.
+pub(crate) struct Targets<'a> {
+    pub(crate) targets: &'a [f64],
+    pub(crate) criterion: RegressionCriterion,
+}
+
+impl Criterion for Targets<'_> {
+    fn node_value(&self, samples: &[usize]) -> Vec<f64> {
+        let mut values: Vec<f64> = samples.iter().map(|&i| self.targets[i]).collect();
+        match self.criterion {
+            RegressionCriterion::SquaredError => {
+                vec![values.iter().sum::<f64>() / values.len() as f64]
+            }
+            RegressionCriterion::AbsoluteError => {
+                values.sort_by(f64::total_cmp);
+                let middle = values.len() / 2;
+                if values.len().is_multiple_of(2) {
+                    vec![(values[middle - 1] + values[middle]) / 2.0]
+                } else {
+                    vec![values[middle]]
+                }
+            }
+        }
+    }
+
+    fn impurity(&self, samples: &[usize]) -> f64 {
+        *self
+            .prefix_impurities(samples.iter().copied())
+            .last()
+            .unwrap()
+    }
+
+    fn prefix_impurities(&self, samples: impl Iterator<Item = usize>) -> Vec<f64> {
+        match self.criterion {
+            RegressionCriterion::SquaredError => {
+                let (mut sum, mut sum_squares) = (0.0, 0.0);
+                samples
+                    .enumerate()
+                    .map(|(k, i)| {
+                        let y = self.targets[i];
+                        sum += y;
+                        sum_squares += y * y;
+                        let n = (k + 1) as f64;
+                        (sum_squares / n - (sum / n).powi(2)).max(0.0)
+                    })
+                    .collect()
+            }
+            RegressionCriterion::AbsoluteError => {
+                let mut median = RunningMedian::default();
+                samples
+                    .map(|i| {
+                        median.push(self.targets[i]);
+                        median.mean_absolute_deviation()
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Totally ordered `f64`, for heaps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Ordered(f64);
+
+impl Eq for Ordered {}
+
+impl PartialOrd for Ordered {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ordered {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Median of a growing set of values, kept as a max-heap of the lower half and a min-heap of
+/// the upper half, along with the sum of each half.
+#[derive(Default)]
+struct RunningMedian {
+    lower: BinaryHeap<Ordered>,
+    upper: BinaryHeap<Reverse<Ordered>>,
+    lower_sum: f64,
+    upper_sum: f64,
+}
+
+impl RunningMedian {
+    fn push(&mut self, value: f64) {
+        if self.lower.peek().is_none_or(|top| value <= top.0) {
+            self.lower.push(Ordered(value));
+            self.lower_sum += value;
+        } else {
+            self.upper.push(Reverse(Ordered(value)));
+            self.upper_sum += value;
+        }
+
+        // Keep the lower half as large as the upper half, or one value larger.
+        if self.lower.len() > self.upper.len() + 1 {
+            let Ordered(moved) = self.lower.pop().unwrap();
+            self.lower_sum -= moved;
+            self.upper.push(Reverse(Ordered(moved)));
+            self.upper_sum += moved;
+        } else if self.upper.len() > self.lower.len() {
+            let Reverse(Ordered(moved)) = self.upper.pop().unwrap();
+            self.upper_sum -= moved;
+            self.lower.push(Ordered(moved));
+            self.lower_sum += moved;
+        }
+    }
+
+    /// Mean absolute deviation from the median, which is the same for any median between the
+    /// two middle values.
+    fn mean_absolute_deviation(&self) -> f64 {
+        let median = self.lower.peek().map_or(0.0, |top| top.0);
+        let below = median * self.lower.len() as f64 - self.lower_sum;
+        let above = self.upper_sum - median * self.upper.len() as f64;
+        (below + above) / (self.lower.len() + self.upper.len()) as f64
+    }
+}
+
+#[test]
+fn split_impurities() {
+    let classes = Classes {
+        classes: &[0, 0, 1, 1],
+        num_classes: 2,
+        criterion: ClassificationCriterion::Gini,
+    };
+    let splits = classes.split_impurities(&[0, 1, 2, 3]);
+    assert_eq!(splits, [(0.0, 4.0 / 9.0), (0.0, 0.0), (4.0 / 9.0, 0.0)]);
+    assert_eq!(classes.impurity(&[0, 2]), 0.5);
+
+    let entropy = Classes {
+        criterion: ClassificationCriterion::Entropy,
+        ..classes
+    };
+    assert_eq!(entropy.impurity(&[0, 1, 2, 3]), 1.0);
+
+    let targets = [1.0, 2.0, 4.0, 10.0, 0.0];
+    let mse = Targets {
+        targets: &targets,
+        criterion: RegressionCriterion::SquaredError,
+    };
+    let mae = Targets {
+        targets: &targets,
+        criterion: RegressionCriterion::AbsoluteError,
+    };
+    let brute_force = |samples: &[usize], absolute: bool| {
+        let mut values: Vec<f64> = samples.iter().map(|&i| targets[i]).collect();
+        values.sort_by(f64::total_cmp);
+        let n = values.len() as f64;
+        let center = if absolute {
+            values[values.len() / 2]
+        } else {
+            values.iter().sum::<f64>() / n
+        };
+        let power = if absolute { 1 } else { 2 };
+        values
+            .iter()
+            .map(|v| (v - center).abs().powi(power))
+            .sum::<f64>()
+            / n
+    };
+
+    let samples = [0, 1, 2, 3, 4];
+    for k in 1..samples.len() {
+        let (left, right) = samples.split_at(k);
+        let (mse_left, mse_right) = mse.split_impurities(&samples)[k - 1];
+        assert!((mse_left - brute_force(left, false)).abs() < 1e-12);
+        assert!((mse_right - brute_force(right, false)).abs() < 1e-12);
+        let (mae_left, mae_right) = mae.split_impurities(&samples)[k - 1];
+        assert!((mae_left - brute_force(left, true)).abs() < 1e-12);
+        assert!((mae_right - brute_force(right, true)).abs() < 1e-12);
+    }
+    assert_eq!(mae.node_value(&samples), [2.0]);
+}
@@ -0,0 +1,361 @@
+use crate::matrix::Matrix;
+use crate::utils::random::Rng;
+
+mod classifier;
+mod criterion;
+mod regressor;
+
+pub use classifier::DecisionTreeClassifier;
+pub use criterion::{ClassificationCriterion, RegressionCriterion};
+pub use regressor::DecisionTreeRegressor;
+
+pub(crate) use criterion::{Classes, Criterion, Targets};
+
+/// Number of features considered when looking for the best split of a node.
+///
+/// When fewer than all features are considered, they are drawn at random for each node, and
+/// more are drawn if none of them can split the node.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MaxFeatures {
+    /// Every feature (the default).
+    #[default]
+    All,
+
+    /// Square root of the number of features.
+    Sqrt,
+
+    /// Base 2 logarithm of the number of features.
+    Log2,
+
+    Count(usize),
+
+    /// Fraction (`0.0..=1.0`) of the features.
+    Fraction(f32),
+}
+
+impl MaxFeatures {
+    /// Number of features to consider out of `num_features`, at least 1.
+    pub fn of(&self, num_features: usize) -> usize {
+        let count = match *self {
+            MaxFeatures::All => num_features,
+            MaxFeatures::Sqrt => (num_features as f64).sqrt() as usize,
+            MaxFeatures::Log2 => (num_features as f64).log2() as usize,
+            MaxFeatures::Count(count) => count,
+            MaxFeatures::Fraction(fraction) => (fraction * num_features as f32) as usize,
+        };
+        count.clamp(1, num_features.max(1))
+    }
+}
+
+/// How the threshold of a split is chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Splitter {
+    /// The best threshold of each candidate feature.
+    Best,
+
+    /// A random threshold between the smallest and largest value of each candidate feature, as
+    /// in extremely randomized trees.
+    Random,
+}
+
+/// Settings that control the growth of a tree.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TreeParams {
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) min_samples_split: usize,
+    pub(crate) min_samples_leaf: usize,
+    pub(crate) max_features: MaxFeatures,
+    pub(crate) splitter: Splitter,
+}
+
+impl Default for TreeParams {
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            min_samples_split: 2,
+            min_samples_leaf: 1,
+            max_features: MaxFeatures::All,
+            splitter: Splitter::Best,
+        }
+    }
+}
+
+/// Builder methods for the [TreeParams] in the `params` field of an estimator.
+macro_rules! tree_params_builders {
+    () => {
+        /// Maximum depth of the tree (unlimited by default).
+        pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+            self.params.max_depth = Some(max_depth);
+            self
+        }
+
+        /// Minimum number of samples to split a node (2 by default).
+        pub fn with_min_samples_split(mut self, min_samples_split: usize) -> Self {
+            assert!(min_samples_split >= 2, "Invalid minimum number of samples");
+            self.params.min_samples_split = min_samples_split;
+            self
+        }
+
+        /// Minimum number of samples in each leaf (1 by default).
+        pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+            assert!(min_samples_leaf >= 1, "Invalid minimum number of samples");
+            self.params.min_samples_leaf = min_samples_leaf;
+            self
+        }
+
+        /// Number of features considered for each split (all by default).
+        pub fn with_max_features(mut self, max_features: $crate::tree::MaxFeatures) -> Self {
+            self.params.max_features = max_features;
+            self
+        }
+    };
+}
+
+pub(crate) use tree_params_builders;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Split {
+    pub(crate) feature: usize,
+
+    /// Samples whose feature is at most the threshold go left.
+    pub(crate) threshold: f32,
+    pub(crate) left: usize,
+    pub(crate) right: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Node {
+    /// Prediction of the node: the fraction of each class, or a single value.
+    pub(crate) value: Vec<f64>,
+    pub(crate) impurity: f64,
+    pub(crate) num_samples: usize,
+    pub(crate) split: Option<Split>,
+}
+
+/// A fitted binary decision tree, with its root first.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Tree {
+    pub(crate) nodes: Vec<Node>,
+    pub(crate) num_features: usize,
+}
+
+/// Best split found so far for a node.
+struct Candidate {
+    feature: usize,
+    threshold: f32,
+    num_left: usize,
+    score: f64,
+}
+
+impl Tree {
+    /// Grows a tree on the rows of `x` listed in `samples`, which may repeat rows (e.g. for
+    /// bootstrap samples).
+    pub(crate) fn build(
+        x: &Matrix,
+        criterion: &impl Criterion,
+        samples: Vec<usize>,
+        params: &TreeParams,
+        rng: &mut Rng,
+    ) -> Self {
+        assert!(!samples.is_empty(), "No samples");
+        let mut tree = Self {
+            nodes: Vec::new(),
+            num_features: x.num_cols(),
+        };
+        tree.grow(x, criterion, samples, 0, params, rng);
+        tree
+    }
+
+    /// Adds the node holding `samples` and its descendants, and returns its position.
+    fn grow(
+        &mut self,
+        x: &Matrix,
+        criterion: &impl Criterion,
+        samples: Vec<usize>,
+        depth: usize,
+        params: &TreeParams,
+        rng: &mut Rng,
+    ) -> usize {
+        let node = self.nodes.len();
+        let impurity = criterion.impurity(&samples);
+        self.nodes.push(Node {
+            value: criterion.node_value(&samples),
+            impurity,
+            num_samples: samples.len(),
+            split: None,
+        });
+
+        let can_split = params.max_depth.is_none_or(|max| depth < max)
+            && samples.len() >= params.min_samples_split
+            && samples.len() >= 2 * params.min_samples_leaf
+            && impurity > 1e-12;
+        if !can_split {
+            return node;
+        }
+        let Some(best) = find_split(x, criterion, &samples, params, rng) else {
+            return node;
+        };
+
+        let (left, right): (Vec<usize>, Vec<usize>) = samples
+            .into_iter()
+            .partition(|&i| x[(i, best.feature)] <= best.threshold);
+        debug_assert_eq!(left.len(), best.num_left);
+        let left = self.grow(x, criterion, left, depth + 1, params, rng);
+        let right = self.grow(x, criterion, right, depth + 1, params, rng);
+        self.nodes[node].split = Some(Split {
+            feature: best.feature,
+            threshold: best.threshold,
+            left,
+            right,
+        });
+        node
+    }
+
+    /// Returns the position of the leaf that `row` falls into.
+    pub(crate) fn leaf(&self, row: &[f32]) -> usize {
+        assert_eq!(row.len(), self.num_features, "Invalid number of columns");
+        let mut node = 0;
+        while let Some(split) = &self.nodes[node].split {
+            node = if row[split.feature] <= split.threshold {
+                split.left
+            } else {
+                split.right
+            };
+        }
+        node
+    }
+
+    /// Prediction of the leaf that `row` falls into.
+    pub(crate) fn value(&self, row: &[f32]) -> &[f64] {
+        &self.nodes[self.leaf(row)].value
+    }
+
+    /// Total impurity decrease brought by the splits on each feature, weighted by the number of
+    /// samples that reach them, and normalized to sum to 1 (unless the tree has no split).
+    pub(crate) fn importances(&self) -> Vec<f64> {
+        let mut importances = vec![0.0; self.num_features];
+        for node in &self.nodes {
+            if let Some(split) = &node.split {
+                let weighted = |node: &Node| node.num_samples as f64 * node.impurity;
+                importances[split.feature] += weighted(node)
+                    - weighted(&self.nodes[split.left])
+                    - weighted(&self.nodes[split.right]);
+            }
+        }
+        let total: f64 = importances.iter().sum();
+        if total > 0.0 {
+            importances.iter_mut().for_each(|v| *v /= total);
+        }
+        importances
+    }
+
+    /// Number of splits on the longest path from the root to a leaf.
+    pub(crate) fn depth(&self) -> usize {
+        fn depth(nodes: &[Node], node: usize) -> usize {
+            match &nodes[node].split {
+                Some(split) => 1 + depth(nodes, split.left).max(depth(nodes, split.right)),
+                None => 0,
+            }
+        }
+        depth(&self.nodes, 0)
+    }
+
+    pub(crate) fn num_leaves(&self) -> usize {
+        self.nodes
+            .iter()
+            .filter(|node| node.split.is_none())
+            .count()
+    }
+}
+
+/// Finds the split of `samples` with the lowest weighted impurity of its children, among the
+/// features drawn for the node.
+fn find_split(
+    x: &Matrix,
+    criterion: &impl Criterion,
+    samples: &[usize],
+    params: &TreeParams,
+    rng: &mut Rng,
+) -> Option<Candidate> {
+    let n = samples.len();
+    let min_leaf = params.min_samples_leaf;
+    let max_features = params.max_features.of(x.num_cols());
+
+    let mut features: Vec<usize> = (0..x.num_cols()).collect();
+    if max_features < features.len() {
+        rng.shuffle(&mut features);
+    }
+
+    let mut best: Option<Candidate> = None;
+    let mut tried = 0;
+    for feature in features {
+        if tried >= max_features && best.is_some() {
+            break;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|&a, &b| x[(a, feature)].total_cmp(&x[(b, feature)]));
+        let value = |k: usize| x[(sorted[k], feature)];
+        if value(0) == value(n - 1) {
+            // Constant features cannot split the node, and do not count as tried.
+            continue;
+        }
+        tried += 1;
+
+        let mut consider = |num_left: usize, threshold: f32, left: f64, right: f64| {
+            let score = (num_left as f64 * left + (n - num_left) as f64 * right) / n as f64;
+            if best.as_ref().is_none_or(|best| score < best.score) {
+                best = Some(Candidate {
+                    feature,
+                    threshold,
+                    num_left,
+                    score,
+                });
+            }
+        };
+
+        match params.splitter {
+            Splitter::Best => {
+                let impurities = criterion.split_impurities(&sorted);
+                for num_left in min_leaf..=n - min_leaf {
+                    let (below, above) = (value(num_left - 1), value(num_left));
+                    if below == above {
+                        continue;
+                    }
+                    // Midpoint, unless rounding puts it on the value above.
+                    let middle = below + (above - below) / 2.0;
+                    let threshold = if middle < above { middle } else { below };
+                    let (left, right) = impurities[num_left - 1];
+                    consider(num_left, threshold, left, right);
+                }
+            }
+            Splitter::Random => {
+                let (min, max) = (value(0), value(n - 1));
+                let mut threshold = rng.uniform(min as f64, max as f64) as f32;
+                if threshold >= max {
+                    threshold = min;
+                }
+                let num_left = sorted.partition_point(|&i| x[(i, feature)] <= threshold);
+                if num_left >= min_leaf && n - num_left >= min_leaf {
+                    let left = criterion.impurity(&sorted[..num_left]);
+                    let right = criterion.impurity(&sorted[num_left..]);
+                    consider(num_left, threshold, left, right);
+                }
+            }
+        }
+    }
+    best
+}
+
+/// Pairs each feature name, typically from the `FEATURE_NAMES` of the dataset, with its
+/// importance.
+pub(crate) fn name_importances<'a>(
+    importances: Vec<f64>,
+    feature_names: &[&'a str],
+) -> Vec<(&'a str, f64)> {
+    assert_eq!(
+        feature_names.len(),
+        importances.len(),
+        "Invalid number of names"
+    );
+    feature_names.iter().copied().zip(importances).collect()
+}
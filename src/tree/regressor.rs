@@ -0,0 +1,124 @@
+use super::{
+    name_importances, tree_params_builders, RegressionCriterion, Targets, Tree, TreeParams,
+};
+use crate::matrix::Matrix;
+use crate::utils::random::Rng;
+
+/// CART decision tree regressor: recursively splits the samples on a feature threshold, choosing
+/// the split that most reduces the error of predicting each leaf by a single value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecisionTreeRegressor {
+    criterion: RegressionCriterion,
+    params: TreeParams,
+    seed: u64,
+    fitted: Option<Tree>,
+}
+
+impl DecisionTreeRegressor {
+    /// Fully grown tree with the [squared error](RegressionCriterion::SquaredError) criterion.
+    pub fn new() -> Self {
+        Self {
+            criterion: RegressionCriterion::SquaredError,
+            params: TreeParams::default(),
+            seed: 0,
+            fitted: None,
+        }
+    }
+
+    pub fn with_criterion(mut self, criterion: RegressionCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+
+    tree_params_builders!();
+
+    /// Seed of the draw of features for each split, when not all of them are considered.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Fits the tree to the rows of `x` and their targets `y`.
+    pub fn fit<Y: Copy + Into<f64>>(&mut self, x: &Matrix, y: &[Y]) {
+        assert_eq!(x.num_rows(), y.len(), "Inconsistent number of samples");
+        let targets: Vec<f64> = y.iter().map(|&v| v.into()).collect();
+        let criterion = Targets {
+            targets: &targets,
+            criterion: self.criterion,
+        };
+        let samples = (0..y.len()).collect();
+        let mut rng = Rng::new(self.seed);
+        self.fitted = Some(Tree::build(x, &criterion, samples, &self.params, &mut rng));
+    }
+
+    /// Predicts the target of each row of `x`: the mean (or median, for the absolute error
+    /// criterion) of its leaf.
+    ///
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x: &Matrix) -> Vec<f32> {
+        let tree = self.fitted.as_ref().expect("Model not fitted");
+        x.rows().map(|row| tree.value(row)[0] as f32).collect()
+    }
+
+    /// Share of the total impurity decrease brought by the splits on each feature, once fitted.
+    pub fn feature_importances(&self) -> Option<Vec<f64>> {
+        self.fitted.as_ref().map(Tree::importances)
+    }
+
+    /// Pairs each feature name, typically from the `FEATURE_NAMES` of the dataset, with its
+    /// importance, once fitted.
+    pub fn named_importances<'a>(&self, feature_names: &[&'a str]) -> Option<Vec<(&'a str, f64)>> {
+        Some(name_importances(self.feature_importances()?, feature_names))
+    }
+
+    /// Number of splits on the longest path from the root to a leaf, once fitted.
+    pub fn depth(&self) -> Option<usize> {
+        self.fitted.as_ref().map(Tree::depth)
+    }
+
+    /// Number of leaves, once fitted.
+    pub fn num_leaves(&self) -> Option<usize> {
+        self.fitted.as_ref().map(Tree::num_leaves)
+    }
+}
+
+impl Default for DecisionTreeRegressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn diabetes_tree() {
+    use crate::datasets::diabetes::{self, Diabetes};
+    use crate::datasets::Dataset;
+    use crate::metrics::regression::r2_score;
+
+    let (x, y) = Diabetes::to_xy(&diabetes::DATA);
+
+    let mut full = DecisionTreeRegressor::new();
+    full.fit(&x, &y);
+    assert!(r2_score(&y, &full.predict(&x)) > 0.999);
+
+    let mut shallow = DecisionTreeRegressor::new().with_max_depth(3);
+    shallow.fit(&x, &y);
+    assert_eq!(shallow.num_leaves(), Some(8));
+    let r2 = r2_score(&y, &shallow.predict(&x));
+    assert!(r2 > 0.4 && r2 < 0.8);
+
+    // Body mass index and triglycerides carry most of the signal.
+    let mut ranked = shallow.named_importances(&Diabetes::FEATURE_NAMES).unwrap();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    assert!(ranked[0].0.starts_with("ltg") && ranked[1].0.starts_with("bmi"));
+
+    // Median leaves ignore the outliers of each leaf.
+    let mut absolute = DecisionTreeRegressor::new()
+        .with_criterion(RegressionCriterion::AbsoluteError)
+        .with_max_depth(3)
+        .with_min_samples_leaf(10);
+    absolute.fit(&x, &y);
+    let predictions = absolute.predict(&x);
+    assert!(predictions
+        .iter()
+        .all(|p| p.fract() == 0.0 || p.fract() == 0.5));
+}
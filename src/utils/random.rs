@@ -0,0 +1,184 @@
+/// A small, fast, seedable pseudo-random number generator
+/// ([xoshiro256**](https://prng.di.unimi.it/)).
+///
+/// The same seed yields the same sequence on every platform, which keeps experiments reproducible.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // The state is expanded from the seed with SplitMix64, as recommended by the authors of
+        // xoshiro, so that similar seeds still give unrelated sequences.
+        let mut seed = seed;
+        let mut next = || {
+            seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+
+        Self {
+            state: [next(), next(), next(), next()],
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+
+        result
+    }
+
+    /// Returns an integer uniformly distributed in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Invalid range");
+
+        // Lemire's multiply-and-reject method, which avoids the bias of a plain modulo.
+        let n = n as u64;
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let product = self.next_u64() as u128 * n as u128;
+            if product as u64 >= threshold {
+                return (product >> 64) as usize;
+            }
+        }
+    }
+
+    /// Returns a float uniformly distributed in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        // The top 53 bits fill the mantissa exactly.
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Returns a float uniformly distributed in `[0, 1)`.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    /// Returns a float uniformly distributed in `[low, high)`.
+    pub fn uniform(&mut self, low: f64, high: f64) -> f64 {
+        assert!(low < high, "Invalid range");
+        low + (high - low) * self.next_f64()
+    }
+
+    /// Returns a normally distributed float (Box–Muller transform).
+    ///
+    /// Unlike the other methods, which only use integer and exactly rounded float arithmetic,
+    /// this relies on `ln` and `cos`, whose last bit may differ between platforms.
+    pub fn normal(&mut self, mean: f64, std: f64) -> f64 {
+        // `1 - u` lies in `(0, 1]`, which keeps the logarithm finite.
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        mean + std * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+
+    /// Returns an index drawn with probability proportional to its (non-negative) weight.
+    pub fn categorical(&mut self, weights: &[f64]) -> usize {
+        let total: f64 = weights.iter().sum();
+        assert!(total > 0.0, "Weights must not all be zero");
+
+        let mut x = self.next_f64() * total;
+        for (i, &weight) in weights.iter().enumerate() {
+            if x < weight {
+                return i;
+            }
+            x -= weight;
+        }
+
+        // Rounding can leave `x` slightly above the last weight.
+        weights.iter().rposition(|&w| w > 0.0).unwrap()
+    }
+
+    /// Returns a uniformly chosen element of `values`.
+    pub fn choose<'a, T>(&mut self, values: &'a [T]) -> &'a T {
+        &values[self.below(values.len())]
+    }
+
+    /// Shuffles `values` in place (Fisher–Yates).
+    pub fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            values.swap(i, self.below(i + 1));
+        }
+    }
+
+    /// Returns `k` distinct indices drawn uniformly from `0..n`, in the order they were drawn.
+    pub fn sample(&mut self, n: usize, k: usize) -> Vec<usize> {
+        assert!(k <= n, "Cannot sample more values than there are");
+
+        // A Fisher–Yates shuffle stopped after `k` steps.
+        let mut indices: Vec<usize> = (0..n).collect();
+        for i in 0..k {
+            indices.swap(i, i + self.below(n - i));
+        }
+        indices.truncate(k);
+        indices
+    }
+}
+
+// The expected values below were computed with an independent implementation of the same
+// algorithms, and must never change: they pin the sequences of every seed.
+#[test]
+fn pinned_sequences() {
+    let mut rng = Rng::new(42);
+    assert_eq!(
+        [rng.next_u64(), rng.next_u64(), rng.next_u64()],
+        [
+            1546998764402558742,
+            6990951692964543102,
+            12544586762248559009
+        ]
+    );
+
+    let mut rng = Rng::new(42);
+    assert_eq!(
+        (0..8).map(|_| rng.below(10)).collect::<Vec<_>>(),
+        [0, 3, 6, 9, 9, 7, 7, 8]
+    );
+
+    let mut rng = Rng::new(42);
+    let mut values: Vec<usize> = (0..10).collect();
+    rng.shuffle(&mut values);
+    assert_eq!(values, [9, 1, 4, 2, 8, 7, 6, 5, 3, 0]);
+
+    let mut rng = Rng::new(42);
+    assert_eq!(rng.next_f64(), 0.08386297105988216);
+    assert_eq!(rng.next_f64(), 0.3789802506626686);
+
+    let mut rng = Rng::new(42);
+    assert_eq!(rng.sample(10, 4), [0, 4, 7, 9]);
+
+    let mut rng = Rng::new(42);
+    for expected in [-0.303263064678738, 1.3438117634372806, 0.3834617912676943] {
+        assert!((rng.normal(0.0, 1.0) - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn distributions() {
+    let mut rng = Rng::new(42);
+    let mut counts = [0; 3];
+    for _ in 0..10000 {
+        counts[rng.categorical(&[1.0, 2.0, 7.0])] += 1;
+    }
+    assert_eq!(counts, [1045, 2001, 6954]);
+
+    let mut rng = Rng::new(7);
+    let draws: Vec<f64> = (0..10000).map(|_| rng.normal(5.0, 2.0)).collect();
+    let mean = draws.iter().sum::<f64>() / draws.len() as f64;
+    let var = draws.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / draws.len() as f64;
+    assert!((mean - 5.0).abs() < 0.05);
+    assert!((var.sqrt() - 2.0).abs() < 0.05);
+
+    assert!((0..1000).all(|_| (-1.0..3.0).contains(&rng.uniform(-1.0, 3.0))));
+}
@@ -1,3 +1,472 @@
-pub fn scale(values: &Vec<f32>) -> Vec<f32> {
-    todo!("Implement the scale fn")
+/// Standardizes `values` to zero mean and unit (population) variance.
+///
+/// Constant inputs are only centered, since they have no variance to scale.
+pub fn scale(values: &[f32]) -> Vec<f32> {
+    let n = values.len() as f64;
+    let mean = values.iter().map(|&v| v as f64).sum::<f64>() / n;
+    let var = values
+        .iter()
+        .map(|&v| (v as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    let std = if var > 0.0 { var.sqrt() } else { 1.0 };
+
+    values
+        .iter()
+        .map(|&v| ((v as f64 - mean) / std) as f32)
+        .collect()
+}
+
+/// A column-wise transformation whose statistics are learned from training data.
+///
+/// Fitting on a training split and transforming a test split with the same scaler keeps the
+/// statistics of the test split from leaking into the model.
+pub trait Scaler {
+    /// Learns the statistics of each column of `rows`.
+    fn fit<R: AsRef<[f32]>>(&mut self, rows: &[R]);
+
+    /// Scales `rows` with the learned statistics.
+    ///
+    /// Panics if the scaler has not been fitted.
+    fn transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>>;
+
+    /// Undoes [Scaler::transform].
+    ///
+    /// Panics if the scaler has not been fitted.
+    fn inverse_transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>>;
+
+    fn fit_transform<R: AsRef<[f32]>>(&mut self, rows: &[R]) -> Vec<Vec<f32>> {
+        self.fit(rows);
+        self.transform(rows)
+    }
+}
+
+/// Affine map `x -> (x - offset) / scale` applied to each column.
+#[derive(Clone, Debug, PartialEq)]
+struct Affine {
+    offset: Vec<f64>,
+    scale: Vec<f64>,
+}
+
+impl Affine {
+    fn transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>> {
+        self.map(rows, |x, offset, scale| (x - offset) / scale)
+    }
+
+    fn inverse_transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>> {
+        self.map(rows, |x, offset, scale| x * scale + offset)
+    }
+
+    fn map<R: AsRef<[f32]>>(&self, rows: &[R], f: impl Fn(f64, f64, f64) -> f64) -> Vec<Vec<f32>> {
+        rows.iter()
+            .map(|row| {
+                let row = row.as_ref();
+                assert_eq!(row.len(), self.offset.len(), "Invalid number of columns");
+                row.iter()
+                    .zip(self.offset.iter().zip(&self.scale))
+                    .map(|(&x, (&offset, &scale))| f(x as f64, offset, scale) as f32)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Returns the values of each column of `rows` as `f64`s.
+fn columns<R: AsRef<[f32]>>(rows: &[R]) -> Vec<Vec<f64>> {
+    let num_columns = rows.first().map_or(0, |row| row.as_ref().len());
+    let mut columns = vec![Vec::with_capacity(rows.len()); num_columns];
+    for row in rows {
+        let row = row.as_ref();
+        assert_eq!(row.len(), num_columns, "Invalid number of columns");
+        for (column, &x) in columns.iter_mut().zip(row) {
+            column.push(x as f64);
+        }
+    }
+    columns
+}
+
+/// Replaces scales of zero, which come from constant columns, with one.
+fn non_zero(scale: f64) -> f64 {
+    if scale == 0.0 {
+        1.0
+    } else {
+        scale
+    }
+}
+
+/// Linearly interpolated `q`-quantile (`0.0..=1.0`) of sorted, non-empty `values`.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// Standardizes each column by removing its mean and dividing by its standard deviation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StandardScaler {
+    with_mean: bool,
+    with_std: bool,
+    fitted: Option<Affine>,
+}
+
+impl StandardScaler {
+    pub fn new() -> Self {
+        Self {
+            with_mean: true,
+            with_std: true,
+            fitted: None,
+        }
+    }
+
+    /// Whether to center each column on its mean (the default).
+    pub fn with_mean(mut self, with_mean: bool) -> Self {
+        self.with_mean = with_mean;
+        self
+    }
+
+    /// Whether to divide each column by its standard deviation (the default).
+    pub fn with_std(mut self, with_std: bool) -> Self {
+        self.with_std = with_std;
+        self
+    }
+
+    /// Mean of each column, once fitted.
+    pub fn mean(&self) -> Option<&[f64]> {
+        self.fitted.as_ref().map(|a| a.offset.as_slice())
+    }
+
+    /// Scale applied to each column, once fitted.
+    pub fn scale(&self) -> Option<&[f64]> {
+        self.fitted.as_ref().map(|a| a.scale.as_slice())
+    }
+}
+
+impl Default for StandardScaler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scaler for StandardScaler {
+    fn fit<R: AsRef<[f32]>>(&mut self, rows: &[R]) {
+        let (offset, scale) = columns(rows)
+            .iter()
+            .map(|column| {
+                let n = column.len() as f64;
+                let mean = column.iter().sum::<f64>() / n;
+                let var = column.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+                (
+                    if self.with_mean { mean } else { 0.0 },
+                    if self.with_std {
+                        non_zero(var.sqrt())
+                    } else {
+                        1.0
+                    },
+                )
+            })
+            .unzip();
+        self.fitted = Some(Affine { offset, scale });
+    }
+
+    fn transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>> {
+        self.fitted
+            .as_ref()
+            .expect("Scaler not fitted")
+            .transform(rows)
+    }
+
+    fn inverse_transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>> {
+        self.fitted
+            .as_ref()
+            .expect("Scaler not fitted")
+            .inverse_transform(rows)
+    }
+}
+
+/// Scales each column to a given range, `[0, 1]` by default.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinMaxScaler {
+    range: (f32, f32),
+    fitted: Option<Affine>,
+}
+
+impl MinMaxScaler {
+    pub fn new() -> Self {
+        Self {
+            range: (0.0, 1.0),
+            fitted: None,
+        }
+    }
+
+    /// Sets the range the minimum and maximum of each column are mapped to.
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        assert!(min < max, "Invalid range");
+        self.range = (min, max);
+        self
+    }
+}
+
+impl Default for MinMaxScaler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scaler for MinMaxScaler {
+    fn fit<R: AsRef<[f32]>>(&mut self, rows: &[R]) {
+        let (low, high) = (self.range.0 as f64, self.range.1 as f64);
+        let (offset, scale) = columns(rows)
+            .iter()
+            .map(|column| {
+                let min = column.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = column.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let scale = non_zero(max - min) / (high - low);
+                (min - low * scale, scale)
+            })
+            .unzip();
+        self.fitted = Some(Affine { offset, scale });
+    }
+
+    fn transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>> {
+        self.fitted
+            .as_ref()
+            .expect("Scaler not fitted")
+            .transform(rows)
+    }
+
+    fn inverse_transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>> {
+        self.fitted
+            .as_ref()
+            .expect("Scaler not fitted")
+            .inverse_transform(rows)
+    }
+}
+
+/// Divides each column by its maximum absolute value, which keeps zeros (and sparsity) intact.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MaxAbsScaler {
+    fitted: Option<Affine>,
+}
+
+impl MaxAbsScaler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Scaler for MaxAbsScaler {
+    fn fit<R: AsRef<[f32]>>(&mut self, rows: &[R]) {
+        let (offset, scale) = columns(rows)
+            .iter()
+            .map(|column| {
+                let max = column.iter().fold(0.0, |max: f64, x| max.max(x.abs()));
+                (0.0, non_zero(max))
+            })
+            .unzip();
+        self.fitted = Some(Affine { offset, scale });
+    }
+
+    fn transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>> {
+        self.fitted
+            .as_ref()
+            .expect("Scaler not fitted")
+            .transform(rows)
+    }
+
+    fn inverse_transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>> {
+        self.fitted
+            .as_ref()
+            .expect("Scaler not fitted")
+            .inverse_transform(rows)
+    }
+}
+
+/// Centers each column on its median and divides it by its interquartile range, which makes it
+/// robust to outliers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RobustScaler {
+    quantile_range: (f32, f32),
+    fitted: Option<Affine>,
+}
+
+impl RobustScaler {
+    pub fn new() -> Self {
+        Self {
+            quantile_range: (25.0, 75.0),
+            fitted: None,
+        }
+    }
+
+    /// Sets the percentiles (`0.0..=100.0`) the scale is computed between, `(25, 75)` by default.
+    pub fn with_quantile_range(mut self, low: f32, high: f32) -> Self {
+        assert!(0.0 <= low && low < high && high <= 100.0, "Invalid range");
+        self.quantile_range = (low, high);
+        self
+    }
+}
+
+impl Default for RobustScaler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scaler for RobustScaler {
+    fn fit<R: AsRef<[f32]>>(&mut self, rows: &[R]) {
+        let (low, high) = (
+            self.quantile_range.0 as f64 / 100.0,
+            self.quantile_range.1 as f64 / 100.0,
+        );
+        let (offset, scale) = columns(rows)
+            .into_iter()
+            .map(|mut column| {
+                column.sort_by(f64::total_cmp);
+                (
+                    quantile(&column, 0.5),
+                    non_zero(quantile(&column, high) - quantile(&column, low)),
+                )
+            })
+            .unzip();
+        self.fitted = Some(Affine { offset, scale });
+    }
+
+    fn transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>> {
+        self.fitted
+            .as_ref()
+            .expect("Scaler not fitted")
+            .transform(rows)
+    }
+
+    fn inverse_transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>> {
+        self.fitted
+            .as_ref()
+            .expect("Scaler not fitted")
+            .inverse_transform(rows)
+    }
+}
+
+/// Norm used by a [Normalizer].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Norm {
+    /// Sum of the absolute values.
+    L1,
+
+    /// Euclidean length.
+    #[default]
+    L2,
+
+    /// Largest absolute value.
+    Max,
+}
+
+impl Norm {
+    pub fn of(&self, row: &[f32]) -> f32 {
+        let row = row.iter().map(|&x| (x as f64).abs());
+        let norm = match self {
+            Norm::L1 => row.sum(),
+            Norm::L2 => row.map(|x| x * x).sum::<f64>().sqrt(),
+            Norm::Max => row.fold(0.0, f64::max),
+        };
+        norm as f32
+    }
+}
+
+/// Scales each row (rather than each column) to unit norm.
+///
+/// Unlike the column [Scaler]s, a normalizer learns nothing from the data: [Normalizer::fit] only
+/// exists for symmetry, and undoing the transformation requires the original row [norms](Normalizer::norms).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Normalizer {
+    norm: Norm,
+}
+
+impl Normalizer {
+    pub fn new(norm: Norm) -> Self {
+        Self { norm }
+    }
+
+    pub fn fit<R: AsRef<[f32]>>(&mut self, _rows: &[R]) {}
+
+    /// Norm of each row. Rows with a norm of zero are reported with a norm of one, so that they
+    /// are left unchanged.
+    pub fn norms<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<f32> {
+        rows.iter()
+            .map(|row| non_zero(self.norm.of(row.as_ref()) as f64) as f32)
+            .collect()
+    }
+
+    pub fn transform<R: AsRef<[f32]>>(&self, rows: &[R]) -> Vec<Vec<f32>> {
+        rows.iter()
+            .zip(self.norms(rows))
+            .map(|(row, norm)| row.as_ref().iter().map(|x| x / norm).collect())
+            .collect()
+    }
+
+    /// Undoes [Normalizer::transform], given the [norms](Normalizer::norms) of the original rows.
+    pub fn inverse_transform<R: AsRef<[f32]>>(&self, rows: &[R], norms: &[f32]) -> Vec<Vec<f32>> {
+        assert_eq!(rows.len(), norms.len(), "Invalid number of norms");
+        rows.iter()
+            .zip(norms)
+            .map(|(row, norm)| row.as_ref().iter().map(|x| x * norm).collect())
+            .collect()
+    }
+}
+
+#[test]
+fn scalers_round_trip() {
+    let train = [[1.0, -4.0], [2.0, 0.0], [3.0, 2.0], [10.0, 6.0]];
+    let test = [[0.0, 1.0], [4.0, 8.0]];
+
+    let mut standard = StandardScaler::new();
+    let mut min_max = MinMaxScaler::new().with_range(-1.0, 1.0);
+    let mut max_abs = MaxAbsScaler::new();
+    let mut robust = RobustScaler::new();
+
+    let fitted = [
+        standard.fit_transform(&train),
+        min_max.fit_transform(&train),
+        max_abs.fit_transform(&train),
+        robust.fit_transform(&train),
+    ];
+    assert_eq!(fitted[1][0], [-1.0, -1.0]);
+    assert_eq!(fitted[1][3], [1.0, 1.0]);
+    assert_eq!(fitted[2][3], [1.0, 1.0]);
+    // Medians are 2.5 and 1, interquartile ranges are 3 and 4.
+    assert_eq!(fitted[3][0], [-0.5, -1.25]);
+
+    // The first column has a mean of 4 and a standard deviation of sqrt(12.5).
+    let column: Vec<f32> = train.iter().map(|row| row[0]).collect();
+    let scaled_column: Vec<f32> = fitted[0].iter().map(|row| row[0]).collect();
+    assert_eq!(scaled_column, scale(&column));
+    let scaled = standard.transform(&test);
+    assert!((scaled[0][0] + 4.0 / 12.5f32.sqrt()).abs() < 1e-6);
+
+    for restored in [
+        standard.inverse_transform(&scaled),
+        min_max.inverse_transform(&min_max.transform(&test)),
+        max_abs.inverse_transform(&max_abs.transform(&test)),
+        robust.inverse_transform(&robust.transform(&test)),
+    ] {
+        for (restored, original) in restored.iter().flatten().zip(test.iter().flatten()) {
+            assert!((restored - original).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn normalizer() {
+    let rows = [[3.0, -4.0], [0.0, 0.0]];
+
+    for (norm, expected) in [
+        (Norm::L1, [3.0 / 7.0, -4.0 / 7.0]),
+        (Norm::L2, [0.6, -0.8]),
+        (Norm::Max, [0.75, -1.0]),
+    ] {
+        let normalizer = Normalizer::new(norm);
+        let normalized = normalizer.transform(&rows);
+        assert_eq!(normalized, [expected.to_vec(), vec![0.0, 0.0]]);
+
+        let restored = normalizer.inverse_transform(&normalized, &normalizer.norms(&rows));
+        assert_eq!(restored[0], [3.0, -4.0]);
+    }
 }